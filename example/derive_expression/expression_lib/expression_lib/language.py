@@ -1,6 +1,6 @@
 from __future__ import annotations
 
-from typing import TYPE_CHECKING
+from typing import TYPE_CHECKING, Any
 
 import polars as pl
 from polars.plugins import register_plugin_function
@@ -21,6 +21,42 @@ def pig_latinnify(expr: IntoExprColumn, capitalize: bool = False) -> pl.Expr:
     )
 
 
+def pad_start(expr: IntoExprColumn, **kwargs: Any) -> pl.Expr:
+    """
+    This example shows how arbitrary keyword arguments can be passed and read by key.
+    """
+    return register_plugin_function(
+        plugin_path=LIB,
+        args=[expr],
+        function_name="pad_start",
+        is_elementwise=True,
+        kwargs=kwargs,
+    )
+
+
+def reverse_chars(expr: IntoExprColumn) -> pl.Expr:
+    """
+    This example shows how the output keeps the name of the input with `rename=false`.
+    """
+    return register_plugin_function(
+        plugin_path=LIB,
+        args=[expr],
+        function_name="reverse_chars",
+        is_elementwise=True,
+    )
+
+
+def add_one_in_place(expr: IntoExprColumn) -> pl.Expr:
+    """
+    This example shows how a function can mutate its input into the output with `in_place`.
+    """
+    return register_plugin_function(
+        plugin_path=LIB,
+        args=[expr],
+        function_name="add_one_in_place",
+        is_elementwise=True,
+    )
+
 def append_args(
     expr: IntoExprColumn,
     float_arg: float,
@@ -0,0 +1,69 @@
+from __future__ import annotations
+
+import ctypes
+from typing import TYPE_CHECKING
+
+from expression_lib._utils import LIB
+
+if TYPE_CHECKING:
+    from types import TracebackType
+
+
+def _load_library() -> ctypes.CDLL:
+    # The same shared library that polars loads the plugin functions from.
+    path = next(
+        p for p in LIB.iterdir() if p.suffix in (".so", ".pyd", ".dylib")
+    )
+    lib = ctypes.CDLL(str(path))
+    lib._polars_plugin_new_progress.restype = ctypes.c_uint64
+    lib._polars_plugin_get_progress.argtypes = [ctypes.c_uint64]
+    lib._polars_plugin_get_progress.restype = ctypes.c_double
+    lib._polars_plugin_drop_progress.argtypes = [ctypes.c_uint64]
+    return lib
+
+
+class Progress:
+    """
+    The progress of a plugin function that is registered with the `progress` attribute.
+
+    Pass `kwargs` to the plugin function, and poll `value` while the query runs, e.g.
+    from another thread. Every handle tracks its own calls, and is `1.0` once the function
+    has returned.
+    """
+
+    def __init__(self) -> None:
+        self._lib = _load_library()
+        self.id: int | None = self._lib._polars_plugin_new_progress()
+
+    @property
+    def kwargs(self) -> dict[str, int]:
+        """The kwargs that make a plugin function report to this handle."""
+        return {"_progress_id": self._handle()}
+
+    @property
+    def value(self) -> float:
+        """The last reported fraction of work that is done, between 0.0 and 1.0."""
+        return self._lib._polars_plugin_get_progress(self._handle())
+
+    def close(self) -> None:
+        """Release the handle. Functions that still run aren't affected."""
+        if self.id is not None:
+            self._lib._polars_plugin_drop_progress(self.id)
+            self.id = None
+
+    def _handle(self) -> int:
+        if self.id is None:
+            msg = "the progress handle is closed"
+            raise ValueError(msg)
+        return self.id
+
+    def __enter__(self) -> Progress:
+        return self
+
+    def __exit__(
+        self,
+        exc_type: type[BaseException] | None,
+        exc: BaseException | None,
+        tb: TracebackType | None,
+    ) -> None:
+        self.close()
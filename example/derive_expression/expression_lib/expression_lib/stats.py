@@ -0,0 +1,37 @@
+from __future__ import annotations
+
+from typing import TYPE_CHECKING
+
+import polars as pl
+from polars.plugins import register_plugin_function
+
+from expression_lib._utils import LIB
+
+if TYPE_CHECKING:
+    from expression_lib._typing import IntoExprColumn
+    from expression_lib.progress import Progress
+
+
+def weighted_mean(expr: IntoExprColumn, weights: IntoExprColumn) -> pl.Expr:
+    """
+    This example shows how a custom aggregation is registered with `returns_scalar`.
+    """
+    return register_plugin_function(
+        plugin_path=LIB,
+        args=[expr, weights],
+        function_name="weighted_mean",
+        returns_scalar=True,
+    )
+
+
+def sum_with_progress(expr: IntoExprColumn, progress: Progress) -> pl.Expr:
+    """
+    This example shows how a function reports its progress to a handle that is polled from python.
+    """
+    return register_plugin_function(
+        plugin_path=LIB,
+        args=[expr],
+        function_name="sum_with_progress",
+        returns_scalar=True,
+        kwargs=progress.kwargs,
+    )
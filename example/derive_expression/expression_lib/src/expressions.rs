@@ -1,6 +1,8 @@
 use polars::prelude::*;
 use polars_plan::dsl::FieldsMapper;
-use pyo3_polars::derive::{polars_expr, CallerContext};
+use pyo3_polars::derive::{
+    expect_inputs, polars_expr, CallerContext, DefaultKwargs, DefaultKwargsExt, ProgressReporter,
+};
 use pyo3_polars::export::polars_core::POOL;
 use serde::Deserialize;
 use std::fmt::Write;
@@ -99,8 +101,12 @@ fn jaccard_similarity(inputs: &[Series]) -> PolarsResult<Series> {
     crate::distances::naive_jaccard_sim(a, b).map(|ca| ca.into_series())
 }
 
-#[polars_expr(output_type=Float64)]
+#[polars_expr(output_type=Float64, is_elementwise)]
 fn hamming_distance(inputs: &[Series]) -> PolarsResult<Series> {
+    expect_inputs(
+        inputs,
+        &[("expr", DataType::String), ("other", DataType::String)],
+    )?;
     let a = inputs[0].str()?;
     let b = inputs[1].str()?;
     let out: UInt32Chunked =
@@ -108,6 +114,30 @@ fn hamming_distance(inputs: &[Series]) -> PolarsResult<Series> {
     Ok(out.into_series())
 }
 
+/// Reduce the values to a single weighted mean. The function is registered with
+/// `returns_scalar=True` on the python side, so an aggregation yields a value per group.
+#[polars_expr(output_type=Float64, returns_scalar)]
+fn weighted_mean(inputs: &[Series]) -> PolarsResult<Series> {
+    let values = inputs[0].cast(&DataType::Float64)?;
+    let weights = inputs[1].cast(&DataType::Float64)?;
+    let mean = (&values * &weights)?.sum::<f64>()? / weights.sum::<f64>()?;
+    Ok(Series::new(values.name().clone(), [mean]))
+}
+
+/// Sum the values and report the progress to the handle that is passed from python, see
+/// `expression_lib.progress`.
+#[polars_expr(output_type=Int64, progress)]
+fn sum_with_progress(inputs: &[Series], progress: ProgressReporter) -> PolarsResult<Series> {
+    let values = inputs[0].cast(&DataType::Int64)?;
+    let ca = values.i64()?;
+    let mut sum = 0;
+    for (i, value) in ca.iter().enumerate() {
+        sum += value.unwrap_or(0);
+        progress.update_count(i + 1, ca.len());
+    }
+    Ok(Series::new(values.name().clone(), [sum]))
+}
+
 fn haversine_output(input_fields: &[Field]) -> PolarsResult<Field> {
     FieldsMapper::new(input_fields).map_to_float_dtype()
 }
@@ -149,7 +179,8 @@ pub struct MyKwargs {
 
 /// If you want to accept `kwargs`. You define a `kwargs` argument
 /// on the second position in you plugin. You can provide any custom struct that is deserializable
-/// with the pickle protocol (on the rust side).
+/// with the pickle protocol (on the rust side). The struct must own its data (`DeserializeOwned`),
+/// so use `String` instead of `&str`.
 #[polars_expr(output_type=String)]
 fn append_kwargs(input: &[Series], kwargs: MyKwargs) -> PolarsResult<Series> {
     let input = &input[0];
@@ -168,6 +199,49 @@ fn append_kwargs(input: &[Series], kwargs: MyKwargs) -> PolarsResult<Series> {
         .into_series())
 }
 
+/// Accept arbitrary kwargs and read them by key. `width` is required and
+/// `fill_char` falls back to a default if it isn't passed.
+#[polars_expr(output_type=String)]
+fn pad_start(input: &[Series], kwargs: DefaultKwargs) -> PolarsResult<Series> {
+    let width: usize = kwargs.get_required("width")?;
+    let fill_char = kwargs.get_as::<char>("fill_char")?.unwrap_or(' ');
+    let ca = input[0].str()?;
+
+    Ok(ca
+        .apply_into_string_amortized(|val, buf| {
+            let n = width.saturating_sub(val.chars().count());
+            buf.extend(std::iter::repeat(fill_char).take(n));
+            buf.push_str(val);
+        })
+        .into_series())
+}
+
+fn reversed_output(input_fields: &[Field]) -> PolarsResult<Field> {
+    Ok(Field::new(
+        "reversed".into(),
+        input_fields[0].dtype().clone(),
+    ))
+}
+
+/// Reverse every string. The output is named "reversed" by the plugin, but `rename=false`
+/// gives it the name of the input.
+#[polars_expr(output_type_func=reversed_output, rename=false)]
+fn reverse_chars(input: &[Series]) -> PolarsResult<Series> {
+    let ca = input[0].str()?;
+    let out = ca.apply_into_string_amortized(|val, buf| buf.extend(val.chars().rev()));
+    Ok(out.into_series().with_name("reversed".into()))
+}
+
+#[polars_expr(output_type=Int64, in_place, is_elementwise)]
+fn add_one_in_place(inputs: &mut [Series]) -> PolarsResult<()> {
+    let s = std::mem::take(&mut inputs[0]).cast(&DataType::Int64)?;
+    let mut ca = s.i64()?.clone();
+    // Only writes into the buffer if polars doesn't hold it anymore, otherwise copies it.
+    ca.apply_mut(|v| v + 1);
+    inputs[0] = ca.into_series();
+    Ok(())
+}
+
 #[polars_expr(output_type=Boolean)]
 fn is_leap_year(input: &[Series]) -> PolarsResult<Series> {
     let input = &input[0];
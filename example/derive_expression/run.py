@@ -79,5 +79,55 @@ try:
     out.with_columns(pl.col("names").panic.panic())
 except pl.exceptions.ComputeError as e:
     assert "the plugin panicked" in str(e)
+    # the panic message and its location are part of the error
+    assert "not yet implemented" in str(e), e
+    assert "expressions.rs" in str(e), e
+
+# Tests that arbitrary kwargs can be read by key.
+out = df.select(
+    padded=language.pad_start("moons", width=5),
+    padded_fill=language.pad_start("moons", width=5, fill_char="*"),
+)
+assert out["padded"].to_list() == [" full", " half", "  red"]
+assert out["padded_fill"].to_list() == ["*full", "*half", "**red"]
+
+try:
+    df.select(language.pad_start("moons"))
+except pl.exceptions.ComputeError as e:
+    assert "missing required kwarg 'width'" in str(e)
+else:
+    raise AssertionError("expected an error for the missing kwarg")
+
+# Tests that `rename=false` keeps the name of the input.
+out = df.with_columns(language.reverse_chars("names"))
+assert out.columns == df.columns
+assert out["names"].to_list() == ["drahciR", "ecilA", "boB"]
+assert df.lazy().select(language.reverse_chars("moons")).collect_schema().names() == ["moons"]
+
+# Tests that an `in_place` function returns its mutated input and leaves the caller's data as is.
+ints = pl.DataFrame({"a": [1, None, 3]})
+out = ints.with_columns(b=language.add_one_in_place("a"))
+assert out["b"].to_list() == [2, None, 4]
+assert out["a"].to_list() == [1, None, 3]
+assert ints["a"].to_list() == [1, None, 3]
+assert ints.select(language.add_one_in_place(pl.col("a") * 2))["a"].to_list() == [3, None, 7]
+
+# Tests that misuse of a plugin raises an error that names the expected inputs.
+from polars.plugins import register_plugin_function
+from expression_lib._utils import LIB
+
+try:
+    df.select(dist.hamming_distance("names", "dates"))
+except pl.exceptions.ComputeError as e:
+    assert "expected input 1 'other' to be of dtype str, got date" in str(e), e
+else:
+    raise AssertionError("expected an error for the wrong dtype")
+
+try:
+    df.select(register_plugin_function(plugin_path=LIB, args=["names"], function_name="hamming_distance"))
+except pl.exceptions.ComputeError as e:
+    assert "expected 2 inputs (expr: str, other: str), got 1" in str(e), e
+else:
+    raise AssertionError("expected an error for the missing input")
 
 print("finished")
@@ -0,0 +1,30 @@
+import polars as pl
+import pytest
+
+from expression_lib import stats
+from expression_lib.progress import Progress
+
+
+def test_progress_is_finished_on_return() -> None:
+    df = pl.DataFrame({"a": [1, 2, 3]})
+    with Progress() as progress:
+        assert progress.value == 0.0
+        out = df.select(stats.sum_with_progress("a", progress))
+        assert out["a"].to_list() == [6]
+        assert progress.value == 1.0
+
+
+def test_progress_per_handle() -> None:
+    df = pl.DataFrame({"a": [1, 2, 3]})
+    with Progress() as first, Progress() as second:
+        assert first.id != second.id
+        df.select(stats.sum_with_progress("a", first))
+        assert first.value == 1.0
+        assert second.value == 0.0
+
+
+def test_closed_progress() -> None:
+    progress = Progress()
+    progress.close()
+    with pytest.raises(ValueError, match="closed"):
+        progress.value
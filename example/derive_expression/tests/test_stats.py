@@ -0,0 +1,27 @@
+import polars as pl
+from polars.testing import assert_frame_equal
+
+from expression_lib import stats
+
+
+def test_weighted_mean_select() -> None:
+    df = pl.DataFrame({"values": [1.0, 2.0, 3.0], "weights": [1, 1, 2]})
+    out = df.select(stats.weighted_mean("values", "weights"))
+    assert_frame_equal(out, pl.DataFrame({"values": [2.25]}))
+
+
+def test_weighted_mean_group_by() -> None:
+    df = pl.DataFrame(
+        {
+            "group": ["a", "a", "b", "b", "b"],
+            "values": [1.0, 3.0, 2.0, 4.0, 6.0],
+            "weights": [1, 1, 1, 0, 1],
+        }
+    )
+    out = (
+        df.group_by("group", maintain_order=True)
+        .agg(stats.weighted_mean("values", "weights"))
+    )
+    # `returns_scalar=True` gives a value per group instead of a list.
+    expected = pl.DataFrame({"group": ["a", "b"], "values": [2.0, 4.0]})
+    assert_frame_equal(out, expected)
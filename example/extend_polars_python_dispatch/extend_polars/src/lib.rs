@@ -24,6 +24,12 @@ fn debug(pydf: PyDataFrame) -> PyResult<PyDataFrame> {
     Ok(PyDataFrame(df))
 }
 
+/// Report the null counts of all columns as a single row frame.
+#[pyfunction]
+fn null_count(pydf: PyDataFrame) -> PyDataFrame {
+    pydf.null_count()
+}
+
 #[pyfunction]
 fn lazy_parallel_jaccard(pydf: PyLazyFrame, col_a: &str, col_b: &str) -> PyResult<PyLazyFrame> {
     let df: LazyFrame = pydf.into();
@@ -38,5 +44,6 @@ fn extend_polars(_py: Python, m: &Bound<PyModule>) -> PyResult<()> {
     m.add_function(wrap_pyfunction!(parallel_jaccard, m)?)?;
     m.add_function(wrap_pyfunction!(lazy_parallel_jaccard, m)?)?;
     m.add_function(wrap_pyfunction!(debug, m)?)?;
+    m.add_function(wrap_pyfunction!(null_count, m)?)?;
     Ok(())
 }
@@ -1,5 +1,5 @@
 import polars as pl
-from extend_polars import parallel_jaccard, lazy_parallel_jaccard, debug
+from extend_polars import parallel_jaccard, lazy_parallel_jaccard, debug, null_count
 
 df = pl.DataFrame({"list_a": [[1, 2, 3], [5, 5]],
                    "list_b": [[1, 2, 3, 8], [5, 1, 1]]})
@@ -14,3 +14,6 @@ print(lazy_parallel_jaccard(df.lazy(), "list_a", "list_b").collect())
 
 df = pl.DataFrame({"string": ["ab", "c"]})
 print(debug(df))
+
+df = pl.DataFrame({"a": [1, None, 3], "b": [None, None, "c"]})
+print(null_count(df))
@@ -4,7 +4,14 @@ from polars.io.plugins import register_io_source
 import polars as pl
 
 
-def scan_random(samplers: list[Any], size: int = 1000) -> pl.LazyFrame:
+def scan_random(
+    samplers: list[Any],
+    size: int = 1000,
+    schema_overrides: pl.Schema | dict[str, Any] | None = None,
+) -> pl.LazyFrame:
+    if schema_overrides is not None:
+        schema_overrides = list(pl.Schema(schema_overrides).items())
+
     def source_generator(
         with_columns: list[str] | None,
         predicate: pl.Expr | None,
@@ -20,26 +27,20 @@ def scan_random(samplers: list[Any], size: int = 1000) -> pl.LazyFrame:
         if n_rows is not None and n_rows < size:
             new_size = n_rows
 
-        src = RandomSource(samplers, batch_size, new_size)
-        if with_columns is not None:
-            src.set_with_columns(with_columns)
+        # Only sample the columns that polars needs.
+        src = RandomSource(samplers, batch_size, new_size, schema_overrides, with_columns)
 
-        # Set the predicate.
-        predicate_set = True
-        if predicate is not None:
-            try:
-                src.try_set_predicate(predicate)
-            except pl.exceptions.ComputeError:
-                predicate_set = False
+        # Push the predicate down into the source.
+        predicate_set = predicate is None or src.try_set_predicate(predicate)
 
         while (out := src.next()) is not None:
             # If the source could not apply the predicate
             # (because it wasn't able to deserialize it), we do it here.
-            if not predicate_set and predicate is not None:
+            if not predicate_set:
                 out = out.filter(predicate)
 
             yield out
 
     # create src again to compute the schema
-    src = RandomSource(samplers, 0, 0)
+    src = RandomSource(samplers, 0, 0, schema_overrides)
     return register_io_source(callable=source_generator, schema=src.schema())
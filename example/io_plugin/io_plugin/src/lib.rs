@@ -4,7 +4,7 @@ use crate::samplers::PySampler;
 use polars::prelude::*;
 use pyo3::prelude::*;
 use pyo3_polars::error::PyPolarsErr;
-use pyo3_polars::{PyDataFrame, PyExpr, PySchema};
+use pyo3_polars::{PyDataFrame, PyDataType, PyExpr, PySchema};
 
 #[pyclass]
 pub struct RandomSource {
@@ -13,101 +13,179 @@ pub struct RandomSource {
     n_rows: usize,
     predicate: Option<Expr>,
     with_columns: Option<Vec<usize>>,
+    /// User provided dtypes that override the dtypes of the samplers.
+    schema_overrides: Option<Schema>,
 }
 
 #[pymethods]
 impl RandomSource {
     #[new]
-    #[pyo3(signature = (columns, size_hint, n_rows))]
+    #[pyo3(signature = (columns, size_hint, n_rows, schema_overrides=None, with_columns=None))]
     fn new_source(
         columns: Vec<PySampler>,
         size_hint: Option<usize>,
         n_rows: Option<usize>,
-    ) -> Self {
+        schema_overrides: Option<Vec<(String, PyDataType)>>,
+        with_columns: Option<Vec<String>>,
+    ) -> PyResult<Self> {
+        // `n_rows` is the total number of rows the source produces, the samplers are never asked
+        // for more. A batch size of zero would never finish, so it is treated as unset.
         let n_rows = n_rows.unwrap_or(usize::MAX);
-        let size_hint = size_hint.unwrap_or(10_000);
+        let size_hint = size_hint.filter(|&n| n > 0).unwrap_or(10_000);
 
-        Self {
+        let mut source = Self {
             columns,
             size_hint,
             n_rows,
             predicate: None,
             with_columns: None,
+            schema_overrides: None,
+        };
+
+        if let Some(overrides) = schema_overrides {
+            let inferred = source.inferred_schema();
+            let overrides = overrides
+                .into_iter()
+                .map(|(name, dtype)| Field::new(name.into(), dtype.0))
+                .collect::<Schema>();
+
+            // Check upfront that every override can be applied, so that we don't fail halfway
+            // through the scan.
+            for (name, dtype) in overrides.iter() {
+                let Some(inferred_dtype) = inferred.get(name) else {
+                    return Err(PyPolarsErr::from(
+                        polars_err!(ColumnNotFound: "schema override for unknown column '{}'", name),
+                    )
+                    .into());
+                };
+                Series::new_empty(name.clone(), inferred_dtype)
+                    .strict_cast(dtype)
+                    .map_err(|_| {
+                        PyPolarsErr::from(polars_err!(
+                            SchemaMismatch: "cannot cast column '{}' from {} to {}",
+                            name, inferred_dtype, dtype
+                        ))
+                    })?;
+            }
+            source.schema_overrides = Some(overrides);
+        }
+
+        // Only the samplers of the projected columns run, in the order of `with_columns`.
+        if let Some(with_columns) = with_columns {
+            let schema = source.inferred_schema();
+            let indexes = with_columns
+                .iter()
+                .map(|name| {
+                    schema.index_of(name).ok_or_else(|| {
+                        PyPolarsErr::from(
+                            polars_err!(ColumnNotFound: "cannot project unknown column '{}'", name),
+                        )
+                    })
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            source.with_columns = Some(indexes);
         }
+        Ok(source)
     }
 
     fn schema(&self) -> PySchema {
-        let schema = self
-            .columns
-            .iter()
-            .map(|s| {
-                let s = s.0.lock().unwrap();
-                Field::new(s.name().into(), s.dtype())
-            })
-            .collect::<Schema>();
+        let mut schema = self.inferred_schema();
+        if let Some(overrides) = &self.schema_overrides {
+            for (name, dtype) in overrides.iter() {
+                schema.set_dtype(name, dtype.clone());
+            }
+        }
         PySchema(Arc::new(schema))
     }
 
-    fn try_set_predicate(&mut self, predicate: PyExpr) {
-        self.predicate = Some(predicate.0);
+    /// Push the predicate down into the source.
+    ///
+    /// Returns `false` if the predicate can't be deserialized, e.g. because the plugin was
+    /// built for another polars version. The caller must then apply the predicate itself.
+    fn try_set_predicate(&mut self, predicate: &Bound<PyAny>) -> bool {
+        match predicate.extract::<PyExpr>() {
+            Ok(predicate) => {
+                self.predicate = Some(predicate.0);
+                true
+            }
+            Err(_) => false,
+        }
     }
 
-    fn set_with_columns(&mut self, columns: Vec<String>) {
-        let schema = self.schema().0;
+    fn next(&mut self) -> PyResult<Option<PyDataFrame>> {
+        if self.n_rows == 0 {
+            return Ok(None);
+        }
 
-        let indexes = columns
+        // Apply projection pushdown.
+        // This prevents unneeded sampling. The predicate may need columns that aren't
+        // projected, so then all columns are sampled and the projection is applied after
+        // filtering.
+        let indexes = match (&self.with_columns, &self.predicate) {
+            (Some(indexes), None) => indexes.clone(),
+            _ => (0..self.columns.len()).collect(),
+        };
+
+        // Apply slice pushdown.
+        // This prevents unneeded sampling.
+        let n = std::cmp::min(self.size_hint, self.n_rows);
+        let columns = indexes
             .iter()
-            .map(|name| {
-                schema
-                    .index_of(name.as_ref())
-                    .expect("schema should be correct")
+            .map(|&i| {
+                let mut s = self.columns[i].0.lock().unwrap();
+                let s = s.next_n(n);
+
+                // Apply the schema overrides.
+                match self
+                    .schema_overrides
+                    .as_ref()
+                    .and_then(|schema| schema.get(s.name()))
+                {
+                    Some(dtype) => s.strict_cast(dtype).map(IntoColumn::into_column),
+                    None => Ok(s.into_column()),
+                }
             })
-            .collect();
+            .collect::<PolarsResult<Vec<_>>>()
+            .map_err(PyPolarsErr::from)?;
+
+        let mut df = DataFrame::new(columns).map_err(PyPolarsErr::from)?;
+        self.n_rows -= n;
+
+        // Apply predicate pushdown.
+        // This is done after the fact, but there could be sources where this could be applied
+        // lower. Polars doesn't filter the rows again, so this must never return rows that
+        // don't match the predicate.
+        if let Some(predicate) = &self.predicate {
+            df = df
+                .lazy()
+                .filter(predicate.clone())
+                ._with_eager(true)
+                .collect()
+                .map_err(PyPolarsErr::from)?;
+
+            if let Some(indexes) = &self.with_columns {
+                let columns = indexes
+                    .iter()
+                    .map(|&i| df.get_columns()[i].clone())
+                    .collect();
+                df = DataFrame::new(columns).map_err(PyPolarsErr::from)?;
+            }
+        }
 
-        self.with_columns = Some(indexes)
+        Ok(Some(PyDataFrame(df)))
     }
+}
 
-    fn next(&mut self) -> PyResult<Option<PyDataFrame>> {
-        if self.n_rows > 0 {
-            // Apply projection pushdown.
-            // This prevents unneeded sampling.
-            let s_iter = if let Some(idx) = &self.with_columns {
-                Box::new(idx.iter().copied().map(|i| &self.columns[i]))
-                    as Box<dyn Iterator<Item = _>>
-            } else {
-                Box::new(self.columns.iter())
-            };
-
-            let columns = s_iter
-                .map(|s| {
-                    let mut s = s.0.lock().unwrap();
-
-                    // Apply slice pushdown.
-                    // This prevents unneeded sampling.
-                    s.next_n(std::cmp::min(self.size_hint, self.n_rows))
-                        .into_column()
-                })
-                .collect::<Vec<_>>();
-
-            let mut df = DataFrame::new(columns).map_err(PyPolarsErr::from)?;
-            self.n_rows = self.n_rows.saturating_sub(self.size_hint);
-
-            // Apply predicate pushdown.
-            // This is done after the fact, but there could be sources where this could be applied
-            // lower.
-            if let Some(predicate) = &self.predicate {
-                df = df
-                    .lazy()
-                    .filter(predicate.clone())
-                    ._with_eager(true)
-                    .collect()
-                    .map_err(PyPolarsErr::from)?;
-            }
-
-            Ok(Some(PyDataFrame(df)))
-        } else {
-            Ok(None)
-        }
+impl RandomSource {
+    /// The schema as produced by the samplers.
+    fn inferred_schema(&self) -> Schema {
+        self.columns
+            .iter()
+            .map(|s| {
+                let s = s.0.lock().unwrap();
+                Field::new(s.name().into(), s.dtype())
+            })
+            .collect()
     }
 }
 
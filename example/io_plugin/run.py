@@ -12,11 +12,42 @@ lf = scan_random(
 
 # predicate pushdown
 assert lf.filter(pl.col("b0.5")).collect()["b0.5"].all()
+assert (lf.filter(pl.col("uniform") > 60).collect()["uniform"] > 60).all()
+
+# the predicate can use columns that aren't projected
+out = lf.filter(pl.col("uniform") > 60).select("b0.1").collect()
+assert out.columns == ["b0.1"]
+assert out.height < 1000
 
 # slice pushdown
 assert lf.head(100).collect().shape == (100, 3)
+assert lf.head(5).collect().shape == (5, 3)
+assert scan_random([new_uniform("u", low=0, high=1, dtype=pl.Float64, seed=1)], size=10_000_000).head(
+    7
+).collect().shape == (7, 1)
 
 # projection pushdown
 out = lf.select("uniform", "b0.1").collect()
 assert out.shape == (1000, 2)
 assert out.columns == ["uniform", "b0.1"]
+assert lf.select("b0.1", "uniform").collect_schema() == pl.Schema(
+    {"b0.1": pl.Boolean, "uniform": pl.Int32}
+)
+assert lf.select("b0.1", "uniform").collect().columns == ["b0.1", "uniform"]
+
+# schema overrides
+lf = scan_random(
+    [new_uniform("uniform", low=10, high=100, dtype=pl.Int64, seed=2)],
+    schema_overrides={"uniform": pl.Int32},
+)
+assert lf.collect_schema() == pl.Schema({"uniform": pl.Int32})
+assert lf.collect()["uniform"].dtype == pl.Int32
+
+try:
+    scan_random(
+        [new_uniform("uniform", low=10, high=100, dtype=pl.Int64, seed=2)],
+        schema_overrides={"foo": pl.Int32},
+    )
+    raise AssertionError("expected an error for an unknown column")
+except Exception as e:
+    assert "unknown column 'foo'" in str(e)
@@ -24,17 +24,49 @@ pub type OutputAttribute = KeyWordAttribute<keywords::output_type, Ident>;
 pub type OutputFuncAttribute = KeyWordAttribute<keywords::output_type_func, Ident>;
 pub type OutputFuncAttributeWithKwargs =
     KeyWordAttribute<keywords::output_type_func_with_kwargs, Ident>;
+pub type RenameAttribute = KeyWordAttribute<keywords::rename, syn::LitBool>;
+pub type KwargsFormatAttribute = KeyWordAttribute<keywords::kwargs_format, syn::LitStr>;
 
-#[derive(Default, Debug)]
+#[derive(Debug)]
 pub struct ExprsFunctionOptions {
     pub output_dtype: Option<Ident>,
     pub output_type_fn: Option<Ident>,
     pub output_type_fn_kwargs: Option<Ident>,
+    pub consume_inputs: bool,
+    /// Whether the function mutates `&mut [Series]` and the first input is the output.
+    pub in_place: bool,
+    pub progress: bool,
+    /// Whether the function reduces its inputs to a single value.
+    pub returns_scalar: bool,
+    /// Whether the output has the same length as the inputs.
+    pub is_elementwise: bool,
+    /// Whether the output may have another name than the first input.
+    pub rename: bool,
+    /// The format the kwargs are serialized in, `"pickle"` if not given.
+    pub kwargs_format: Option<syn::LitStr>,
+}
+
+impl Default for ExprsFunctionOptions {
+    fn default() -> Self {
+        ExprsFunctionOptions {
+            output_dtype: None,
+            output_type_fn: None,
+            output_type_fn_kwargs: None,
+            consume_inputs: false,
+            in_place: false,
+            progress: false,
+            returns_scalar: false,
+            is_elementwise: false,
+            rename: true,
+            kwargs_format: None,
+        }
+    }
 }
 
 impl Parse for ExprsFunctionOptions {
     fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
         let mut options = ExprsFunctionOptions::default();
+        let mut explicit_rename = None;
 
         while !input.is_empty() {
             let lookahead = input.lookahead1();
@@ -48,9 +80,48 @@ impl Parse for ExprsFunctionOptions {
             } else if lookahead.peek(keywords::output_type_func_with_kwargs) {
                 let attr = input.parse::<OutputFuncAttributeWithKwargs>()?;
                 options.output_type_fn_kwargs = Some(attr.value)
+            } else if lookahead.peek(keywords::consume_inputs) {
+                let _ = input.parse::<keywords::consume_inputs>()?;
+                options.consume_inputs = true;
+            } else if lookahead.peek(keywords::in_place) {
+                let _ = input.parse::<keywords::in_place>()?;
+                options.in_place = true;
+            } else if lookahead.peek(keywords::progress) {
+                let _ = input.parse::<keywords::progress>()?;
+                options.progress = true;
+            } else if lookahead.peek(keywords::returns_scalar) {
+                let _ = input.parse::<keywords::returns_scalar>()?;
+                options.returns_scalar = true;
+            } else if lookahead.peek(keywords::is_elementwise) {
+                let _ = input.parse::<keywords::is_elementwise>()?;
+                options.is_elementwise = true;
+            } else if lookahead.peek(keywords::rename) {
+                let attr = input.parse::<RenameAttribute>()?;
+                options.rename = attr.value.value;
+                explicit_rename = Some(attr.value);
+            } else if lookahead.peek(keywords::kwargs_format) {
+                let attr = input.parse::<KwargsFormatAttribute>()?;
+                options.kwargs_format = Some(attr.value);
             } else {
                 panic!("didn't recognize attribute")
             }
+
+            // Attributes are separated by a comma.
+            if !input.is_empty() {
+                let _: Token![,] = input.parse()?;
+            }
+        }
+
+        // The field of `output_type` always has the name of the first input, the output
+        // series can't have another name.
+        if let (Some(rename), Some(_)) = (&explicit_rename, &options.output_dtype) {
+            if rename.value {
+                return Err(syn::Error::new(
+                    rename.span(),
+                    "`rename=true` can't be combined with `output_type`, whose field always has \
+                    the name of the first input; use `output_type_func` to name the output",
+                ));
+            }
         }
         Ok(options)
     }
@@ -1,3 +1,10 @@
 syn::custom_keyword!(output_type);
 syn::custom_keyword!(output_type_func);
 syn::custom_keyword!(output_type_func_with_kwargs);
+syn::custom_keyword!(consume_inputs);
+syn::custom_keyword!(in_place);
+syn::custom_keyword!(progress);
+syn::custom_keyword!(returns_scalar);
+syn::custom_keyword!(is_elementwise);
+syn::custom_keyword!(rename);
+syn::custom_keyword!(kwargs_format);
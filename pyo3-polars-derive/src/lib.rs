@@ -21,11 +21,28 @@ fn insert_error_function() -> proc_macro2::TokenStream {
     }
 }
 
-fn quote_get_kwargs() -> proc_macro2::TokenStream {
+/// The `pyo3_polars::derive::KwargsFormat` the kwargs are deserialized from.
+fn quote_kwargs_format(options: &attr::ExprsFunctionOptions) -> proc_macro2::TokenStream {
+    match options
+        .kwargs_format
+        .as_ref()
+        .map(|format| format.value())
+        .as_deref()
+    {
+        None | Some("pickle") => quote!(pyo3_polars::derive::KwargsFormat::Pickle),
+        Some("json") => quote!(pyo3_polars::derive::KwargsFormat::Json),
+        Some(format) => panic!(
+            "unknown kwargs_format '{}', expected \"pickle\" or \"json\"",
+            format
+        ),
+    }
+}
+
+fn quote_get_kwargs(kwargs_format: &proc_macro2::TokenStream) -> proc_macro2::TokenStream {
     quote!(
     let kwargs = std::slice::from_raw_parts(kwargs_ptr, kwargs_len);
 
-    let kwargs = match pyo3_polars::derive::_parse_kwargs(kwargs)  {
+    let kwargs = match pyo3_polars::derive::_parse_kwargs_with_format(kwargs, #kwargs_format)  {
         Ok(value) => value,
         Err(err) => {
             let err = polars_err!(InvalidOperation: "could not parse kwargs: '{}'\n\nCheck: registration of kwargs in the plugin.", err);
@@ -37,8 +54,14 @@ fn quote_get_kwargs() -> proc_macro2::TokenStream {
     )
 }
 
-fn quote_call_kwargs(ast: &syn::ItemFn, fn_name: &syn::Ident) -> proc_macro2::TokenStream {
-    let kwargs = quote_get_kwargs();
+fn quote_call_kwargs(
+    ast: &syn::ItemFn,
+    fn_name: &syn::Ident,
+    inputs: &proc_macro2::TokenStream,
+    extra_args: &proc_macro2::TokenStream,
+    kwargs_format: &proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    let kwargs = quote_get_kwargs(kwargs_format);
     quote!(
             // parse the kwargs and assign to `let kwargs`
             #kwargs
@@ -47,12 +70,17 @@ fn quote_call_kwargs(ast: &syn::ItemFn, fn_name: &syn::Ident) -> proc_macro2::To
             #ast
 
             // call the function
-        let result: PolarsResult<polars_core::prelude::Series> = #fn_name(&inputs, kwargs);
+        let result = #fn_name(#inputs, kwargs #extra_args);
 
     )
 }
 
-fn quote_call_context(ast: &syn::ItemFn, fn_name: &syn::Ident) -> proc_macro2::TokenStream {
+fn quote_call_context(
+    ast: &syn::ItemFn,
+    fn_name: &syn::Ident,
+    inputs: &proc_macro2::TokenStream,
+    extra_args: &proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
     quote!(
             let context = *context;
 
@@ -60,38 +88,227 @@ fn quote_call_context(ast: &syn::ItemFn, fn_name: &syn::Ident) -> proc_macro2::T
             #ast
 
             // call the function
-        let result: PolarsResult<polars_core::prelude::Series> = #fn_name(&inputs, context);
+        let result = #fn_name(#inputs, context #extra_args);
     )
 }
 
-fn quote_call_context_kwargs(ast: &syn::ItemFn, fn_name: &syn::Ident) -> proc_macro2::TokenStream {
+fn quote_call_context_kwargs(
+    ast: &syn::ItemFn,
+    fn_name: &syn::Ident,
+    inputs: &proc_macro2::TokenStream,
+    extra_args: &proc_macro2::TokenStream,
+    kwargs_format: &proc_macro2::TokenStream,
+    kwargs_first: bool,
+) -> proc_macro2::TokenStream {
+    // Pass `context` and `kwargs` in the order in which the function declares them.
+    let args = if kwargs_first {
+        quote!(kwargs, context)
+    } else {
+        quote!(context, kwargs)
+    };
+    let kwargs = quote_get_kwargs(kwargs_format);
     quote!(
             let context = *context;
 
-            let kwargs = std::slice::from_raw_parts(kwargs_ptr, kwargs_len);
-
-            let kwargs = match pyo3_polars::derive::_parse_kwargs(kwargs)  {
-                    Ok(value) => value,
-                    Err(err) => {
-                        pyo3_polars::derive::_update_last_error(err);
-                        return;
-                    }
-            };
+            // parse the kwargs and assign to `let kwargs`
+            #kwargs
 
             // define the function
             #ast
 
             // call the function
-        let result: PolarsResult<polars_core::prelude::Series> = #fn_name(&inputs, context, kwargs);
+        let result = #fn_name(#inputs, #args #extra_args);
     )
 }
 
-fn quote_call_no_kwargs(ast: &syn::ItemFn, fn_name: &syn::Ident) -> proc_macro2::TokenStream {
+fn quote_call_no_kwargs(
+    ast: &syn::ItemFn,
+    fn_name: &syn::Ident,
+    inputs: &proc_macro2::TokenStream,
+    extra_args: &proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
     quote!(
             // define the function
             #ast
             // call the function
-            let result: PolarsResult<polars_core::prelude::Series> = #fn_name(&inputs);
+            let result = #fn_name(#inputs #extra_args);
+    )
+}
+
+/// Whether the function returns a `PolarsResult<DataFrame>` instead of a `PolarsResult<Series>`.
+fn returns_dataframe(sig: &syn::Signature) -> bool {
+    let syn::ReturnType::Type(_, ty) = &sig.output else {
+        return false;
+    };
+    let syn::Type::Path(ty) = ty.as_ref() else {
+        return false;
+    };
+    let Some(syn::PathArguments::AngleBracketed(args)) =
+        ty.path.segments.last().map(|segment| &segment.arguments)
+    else {
+        return false;
+    };
+    matches!(
+        args.args.first(),
+        Some(syn::GenericArgument::Type(syn::Type::Path(ty)))
+            if ty.path.segments.last().is_some_and(|segment| segment.ident == "DataFrame")
+    )
+}
+
+/// Returns the code to run before and after the call, to convert the result to a `Series`.
+fn quote_convert_result(
+    sig: &syn::Signature,
+    options: &attr::ExprsFunctionOptions,
+) -> (proc_macro2::TokenStream, proc_macro2::TokenStream) {
+    if !returns_dataframe(sig) {
+        let convert = quote!(
+            let result: PolarsResult<polars_core::prelude::Series> = result;
+        );
+        return (proc_macro2::TokenStream::new(), convert);
+    }
+
+    // The columns of the frame become the fields of a struct series, named after the first
+    // input like the output field. If the output type is computed by a function, the struct
+    // must match it. It is computed before the call, as the inputs may be consumed.
+    let fn_name = &sig.ident;
+    let (expected_output, check_output_type) = match &options.output_type_fn {
+        Some(output_type_fn) => (
+            quote!(
+                let fields = inputs.iter().map(|s| s.field().into_owned()).collect::<Vec<_>>();
+                let expected_output = #output_type_fn(&fields);
+            ),
+            quote!(
+                let result = result.and_then(|out| {
+                    let expected = expected_output?;
+                    if expected.dtype() == out.dtype() {
+                        Ok(out)
+                    } else {
+                        let msg = format!(
+                            "plugin function '{}' returned a frame with dtype {}, but its output type is {}",
+                            stringify!(#fn_name),
+                            out.dtype(),
+                            expected.dtype()
+                        );
+                        Err(polars_core::error::PolarsError::SchemaMismatch(msg.into()))
+                    }
+                });
+            ),
+        ),
+        None => Default::default(),
+    };
+    let prepare = quote!(
+        let output_name = inputs.first().map(|s| s.name().clone()).unwrap_or_default();
+        #expected_output
+    );
+    let convert = quote!(
+        let result: PolarsResult<polars_core::prelude::Series> =
+            result.map(|df: polars_core::prelude::DataFrame| {
+                polars_core::prelude::IntoSeries::into_series(df.into_struct(output_name))
+            });
+
+        #check_output_type
+    );
+    (prepare, convert)
+}
+
+/// Returns the code to run after the call of an `in_place` function, which turns the mutated
+/// first input into the output `Series`.
+fn quote_convert_in_place(sig: &syn::Signature) -> proc_macro2::TokenStream {
+    let fn_name = &sig.ident;
+    // The function returns either `()` or `PolarsResult<()>`.
+    let result = match &sig.output {
+        syn::ReturnType::Default => quote!(
+            let () = result;
+            let result: PolarsResult<()> = Ok(());
+        ),
+        syn::ReturnType::Type(..) => quote!(
+            let result: PolarsResult<()> = result;
+        ),
+    };
+    quote!(
+        #result
+        let result: PolarsResult<polars_core::prelude::Series> = result.and_then(|()| {
+            inputs.into_iter().next().ok_or_else(|| {
+                let msg = format!(
+                    "plugin function '{}' is registered with 'in_place', but was called without inputs",
+                    stringify!(#fn_name)
+                );
+                polars_core::error::PolarsError::ComputeError(msg.into())
+            })
+        });
+    )
+}
+
+/// Returns the code to run before and after the call, to give the output the name of the first
+/// input if the function may not rename its output.
+fn quote_keep_name(rename: bool) -> (proc_macro2::TokenStream, proc_macro2::TokenStream) {
+    if rename {
+        return Default::default();
+    }
+    let prepare = quote!(
+        let input_name = inputs.first().map(|s| s.name().clone());
+    );
+    let keep_name = quote!(
+        let result = result.map(|mut out| {
+            if let Some(name) = input_name {
+                out.rename(name);
+            }
+            out
+        });
+    );
+    (prepare, keep_name)
+}
+
+/// Returns the code to run before and after the call, to check that an elementwise function
+/// doesn't change the length of its inputs. The engine only knows that the function is
+/// elementwise from the python registration, so this only catches mistakes in debug builds.
+fn quote_check_elementwise(
+    fn_name: &syn::Ident,
+    is_elementwise: bool,
+) -> (proc_macro2::TokenStream, proc_macro2::TokenStream) {
+    if !is_elementwise {
+        return Default::default();
+    }
+    let prepare = quote!(
+        let input_len = inputs.iter().map(|s| s.len()).max();
+    );
+    let check = quote!(
+        let result = result.and_then(|out| match input_len {
+            Some(len) if cfg!(debug_assertions) && out.len() != len => {
+                let msg = format!(
+                    "plugin function '{}' is registered with 'is_elementwise', but returned {} values for inputs of length {}",
+                    stringify!(#fn_name),
+                    out.len(),
+                    len
+                );
+                Err(polars_core::error::PolarsError::ComputeError(msg.into()))
+            }
+            _ => Ok(out),
+        });
+    );
+    (prepare, check)
+}
+
+/// Returns the code to run after the call, to check that a function that returns a scalar
+/// doesn't return more than one value. The engine only knows that the output is a scalar from
+/// the python registration, so this only catches mistakes in debug builds.
+fn quote_check_scalar(fn_name: &syn::Ident, returns_scalar: bool) -> proc_macro2::TokenStream {
+    if !returns_scalar {
+        return proc_macro2::TokenStream::new();
+    }
+    quote!(
+        let result = result.and_then(|out| {
+            if cfg!(debug_assertions) && out.len() > 1 {
+                let msg = format!(
+                    "plugin function '{}' is registered with 'returns_scalar', but returned {} values",
+                    stringify!(#fn_name),
+                    out.len()
+                );
+                Err(polars_core::error::PolarsError::ComputeError(msg.into()))
+            } else {
+                Ok(out)
+            }
+        });
     )
 }
 
@@ -108,9 +325,12 @@ fn quote_process_results() -> proc_macro2::TokenStream {
     })
 }
 
-fn create_expression_function(ast: syn::ItemFn) -> proc_macro2::TokenStream {
+fn create_expression_function(
+    ast: syn::ItemFn,
+    options: &attr::ExprsFunctionOptions,
+) -> proc_macro2::TokenStream {
     // count how often the user define a kwargs argument.
-    let args = ast
+    let mut args = ast
         .sig
         .inputs
         .iter()
@@ -130,23 +350,78 @@ fn create_expression_function(ast: syn::ItemFn) -> proc_macro2::TokenStream {
 
     let fn_name = &ast.sig.ident;
     let error_msg_fn = insert_error_function();
+    let kwargs_format = quote_kwargs_format(options);
+
+    // The progress reporter is always passed as the last argument. It reports to the handle
+    // in the kwargs, and is finished when the function returns.
+    let (extra_args, quote_prepare_progress, quote_finish_progress) = if options.progress {
+        match args.pop().as_deref() {
+            Some("progress") => {}
+            _ => panic!("expected a last argument 'progress' when the 'progress' attribute is set"),
+        }
+        (
+            quote!(, progress.clone()),
+            quote!(
+                let progress = pyo3_polars::derive::ProgressReporter::_new(
+                    std::slice::from_raw_parts(kwargs_ptr, kwargs_len),
+                    #kwargs_format,
+                );
+            ),
+            quote!(
+                progress.finish();
+            ),
+        )
+    } else {
+        Default::default()
+    };
+
+    // With `consume_inputs` the imported `Vec<Series>` is moved into the function, with
+    // `in_place` it is borrowed as `&mut [Series]`, otherwise it is borrowed as `&[Series]`.
+    let (inputs, import_inputs) = if options.consume_inputs {
+        (quote!(inputs), quote!(let inputs))
+    } else if options.in_place {
+        (quote!(&mut inputs), quote!(let mut inputs))
+    } else {
+        (quote!(&inputs), quote!(let inputs))
+    };
 
     // Get the tokenstream of the call logic.
     let quote_call = match args.len() {
-        0 => quote_call_no_kwargs(&ast, fn_name),
+        0 => quote_call_no_kwargs(&ast, fn_name, &inputs, &extra_args),
         1 => match args[0].as_str() {
-            "kwargs" => quote_call_kwargs(&ast, fn_name),
-            "context" => quote_call_context(&ast, fn_name),
+            "kwargs" => quote_call_kwargs(&ast, fn_name, &inputs, &extra_args, &kwargs_format),
+            "context" => quote_call_context(&ast, fn_name, &inputs, &extra_args),
             a => panic!("didn't expect argument {}", a),
         },
         2 => match (args[0].as_str(), args[1].as_str()) {
-            ("context", "kwargs") => quote_call_context_kwargs(&ast, fn_name),
-            ("kwargs", "context") => panic!("'kwargs', 'context' order should be reversed"),
+            ("context", "kwargs") => quote_call_context_kwargs(
+                &ast,
+                fn_name,
+                &inputs,
+                &extra_args,
+                &kwargs_format,
+                false,
+            ),
+            ("kwargs", "context") => {
+                quote_call_context_kwargs(&ast, fn_name, &inputs, &extra_args, &kwargs_format, true)
+            }
             (a, b) => panic!("didn't expect arguments {}, {}", a, b),
         },
         _ => panic!("didn't expect so many arguments"),
     };
 
+    let (quote_prepare_result, quote_convert_result) = if options.in_place {
+        (
+            proc_macro2::TokenStream::new(),
+            quote_convert_in_place(&ast.sig),
+        )
+    } else {
+        quote_convert_result(&ast.sig, options)
+    };
+    let (quote_prepare_name, quote_keep_name) = quote_keep_name(options.rename);
+    let (quote_prepare_elementwise, quote_check_elementwise) =
+        quote_check_elementwise(fn_name, options.is_elementwise);
+    let quote_check_scalar = quote_check_scalar(fn_name, options.returns_scalar);
     let quote_process_result = quote_process_results();
     let fn_name = get_expression_function_name(fn_name);
 
@@ -165,17 +440,36 @@ fn create_expression_function(ast: syn::ItemFn) -> proc_macro2::TokenStream {
             return_value: *mut polars_ffi::version_0::SeriesExport,
             context: *mut polars_ffi::version_0::CallerContext
         )  {
+            let _plugin_call = pyo3_polars::derive::_PluginCallGuard::enter();
             let panic_result = std::panic::catch_unwind(move || {
-                let inputs = polars_ffi::version_0::import_series_buffer(e, input_len).unwrap();
+                #import_inputs = polars_ffi::version_0::import_series_buffer(e, input_len).unwrap();
+
+                #quote_prepare_result
+
+                #quote_prepare_name
+
+                #quote_prepare_elementwise
+
+                #quote_prepare_progress
 
                 #quote_call
 
+                #quote_finish_progress
+
+                #quote_convert_result
+
+                #quote_keep_name
+
+                #quote_check_elementwise
+
+                #quote_check_scalar
+
                 #quote_process_result
             });
 
-            if panic_result.is_err() {
+            if let Err(payload) = panic_result {
                 // Set latest to panic;
-                pyo3_polars::derive::_set_panic();
+                pyo3_polars::derive::_set_panic(&*payload);
             }
 
         }
@@ -208,12 +502,28 @@ fn create_field_function(
     fn_name: &syn::Ident,
     dtype_fn_name: &syn::Ident,
     kwargs: bool,
+    kwargs_format: &proc_macro2::TokenStream,
+    rename: bool,
 ) -> proc_macro2::TokenStream {
     let map_field_name = get_field_function_name(fn_name);
     let inputs = quote_get_inputs();
 
+    // The output field gets the name of the first input, like the output series.
+    let keep_name = if rename {
+        proc_macro2::TokenStream::new()
+    } else {
+        quote!(
+            let result = result.map(|mut out: polars_core::prelude::Field| {
+                if let Some(input) = inputs.first() {
+                    out.name = input.name().clone();
+                }
+                out
+            });
+        )
+    };
+
     let call_fn = if kwargs {
-        let kwargs = quote_get_kwargs();
+        let kwargs = quote_get_kwargs(kwargs_format);
         quote! (
             #kwargs
             let result = #dtype_fn_name(&inputs, kwargs);
@@ -233,11 +543,14 @@ fn create_field_function(
             kwargs_ptr: *const u8,
             kwargs_len: usize,
         ) {
+            let _plugin_call = pyo3_polars::derive::_PluginCallGuard::enter();
             let panic_result = std::panic::catch_unwind(move || {
                 #inputs;
 
                 #call_fn;
 
+                #keep_name
+
                 match result {
                     Ok(out) => {
                         let out = polars_core::export::arrow::ffi::export_field_to_c(&out.to_arrow(CompatLevel::newest()));
@@ -250,9 +563,9 @@ fn create_field_function(
                 }
             });
 
-            if panic_result.is_err() {
+            if let Err(payload) = panic_result {
                 // Set latest to panic;
-                pyo3_polars::derive::_set_panic();
+                pyo3_polars::derive::_set_panic(&*payload);
             }
         }
     )
@@ -288,17 +601,36 @@ pub fn polars_expr(attr: TokenStream, input: TokenStream) -> TokenStream {
     let ast = parse_macro_input!(input as syn::ItemFn);
 
     let options = parse_macro_input!(attr as attr::ExprsFunctionOptions);
-    let expanded_field_fn = if let Some(fn_name) = options.output_type_fn {
-        create_field_function(&ast.sig.ident, &fn_name, false)
-    } else if let Some(fn_name) = options.output_type_fn_kwargs {
-        create_field_function(&ast.sig.ident, &fn_name, true)
-    } else if let Some(dtype) = options.output_dtype {
-        create_field_function_from_with_dtype(&ast.sig.ident, dtype)
+    if options.returns_scalar && options.is_elementwise {
+        panic!("a function can't be both 'returns_scalar' and 'is_elementwise'")
+    }
+    if options.in_place && options.consume_inputs {
+        panic!("a function can't be both 'in_place' and 'consume_inputs'")
+    }
+    let kwargs_format = quote_kwargs_format(&options);
+    let expanded_field_fn = if let Some(fn_name) = &options.output_type_fn {
+        create_field_function(
+            &ast.sig.ident,
+            fn_name,
+            false,
+            &kwargs_format,
+            options.rename,
+        )
+    } else if let Some(fn_name) = &options.output_type_fn_kwargs {
+        create_field_function(
+            &ast.sig.ident,
+            fn_name,
+            true,
+            &kwargs_format,
+            options.rename,
+        )
+    } else if let Some(dtype) = &options.output_dtype {
+        create_field_function_from_with_dtype(&ast.sig.ident, dtype.clone())
     } else {
         panic!("didn't understand polars_expr attribute")
     };
 
-    let expanded_expr = create_expression_function(ast);
+    let expanded_expr = create_expression_function(ast, &options);
     let expanded = quote!(
         #expanded_field_fn
 
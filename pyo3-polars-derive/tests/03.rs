@@ -0,0 +1,12 @@
+use polars_core::error::PolarsResult;
+use polars_core::prelude::Series;
+use pyo3_polars_derive::polars_expr;
+
+#[polars_expr(output_type=Int64, consume_inputs)]
+fn add_one(inputs: Vec<Series>) -> PolarsResult<Series> {
+    let mut inputs = inputs;
+    let s = inputs.swap_remove(0);
+    Ok(&s + 1)
+}
+
+fn main() {}
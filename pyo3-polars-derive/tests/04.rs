@@ -0,0 +1,17 @@
+use polars_core::error::PolarsResult;
+use polars_core::prelude::Series;
+use pyo3_polars::derive::ProgressReporter;
+use pyo3_polars_derive::polars_expr;
+
+#[polars_expr(output_type=Int64, progress)]
+fn slow_sum(inputs: &[Series], progress: ProgressReporter) -> PolarsResult<Series> {
+    let mut acc = inputs[0].clone();
+    for (i, s) in inputs[1..].iter().enumerate() {
+        acc = (&acc + s)?;
+        progress.update_count(i + 1, inputs.len() - 1);
+    }
+    progress.finish();
+    Ok(acc)
+}
+
+fn main() {}
@@ -0,0 +1,37 @@
+use polars_core::error::PolarsResult;
+use polars_core::prelude::*;
+use pyo3_polars_derive::polars_expr;
+
+mod ffi;
+
+#[polars_expr(output_type=Float64, returns_scalar)]
+fn weighted_mean(inputs: &[Series]) -> PolarsResult<Series> {
+    let values = inputs[0].cast(&DataType::Float64)?;
+    let weights = inputs[1].cast(&DataType::Float64)?;
+    let total = (&values * &weights)?.sum::<f64>()? / weights.sum::<f64>()?;
+    Ok(Series::new(values.name().clone(), [total]))
+}
+
+#[polars_expr(output_type=Int64, returns_scalar)]
+fn not_a_scalar(inputs: &[Series]) -> PolarsResult<Series> {
+    Ok(inputs[0].clone())
+}
+
+fn main() {
+    let values = Series::new("a".into(), [1i64, 2, 3]);
+    let weights = Series::new("w".into(), [1i64, 1, 2]);
+
+    let out = ffi::call(_polars_plugin_weighted_mean, &[values.clone(), weights]).unwrap();
+    assert_eq!(out.f64().unwrap().get(0), Some(2.25));
+
+    // The number of values is only checked in debug builds.
+    let out = ffi::call(_polars_plugin_not_a_scalar, &[values]);
+    if cfg!(debug_assertions) {
+        assert_eq!(
+            out.unwrap_err(),
+            "plugin function 'not_a_scalar' is registered with 'returns_scalar', but returned 3 values"
+        );
+    } else {
+        assert_eq!(out.unwrap().len(), 3);
+    }
+}
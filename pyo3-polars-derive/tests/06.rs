@@ -0,0 +1,32 @@
+use polars_core::error::PolarsResult;
+use polars_core::prelude::Series;
+use pyo3_polars::derive::{CallerContext, DefaultKwargs};
+use pyo3_polars_derive::polars_expr;
+
+#[polars_expr(output_type=Int64)]
+fn with_context(inputs: &[Series], context: CallerContext) -> PolarsResult<Series> {
+    let _parallel = context.parallel();
+    Ok(inputs[0].clone())
+}
+
+#[polars_expr(output_type=Int64)]
+fn with_context_kwargs(
+    inputs: &[Series],
+    context: CallerContext,
+    kwargs: DefaultKwargs,
+) -> PolarsResult<Series> {
+    let _ = (context.parallel(), kwargs);
+    Ok(inputs[0].clone())
+}
+
+#[polars_expr(output_type=Int64)]
+fn with_kwargs_context(
+    inputs: &[Series],
+    kwargs: DefaultKwargs,
+    context: CallerContext,
+) -> PolarsResult<Series> {
+    let _ = (context.parallel(), kwargs);
+    Ok(inputs[0].clone())
+}
+
+fn main() {}
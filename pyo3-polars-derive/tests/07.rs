@@ -0,0 +1,26 @@
+use polars_core::error::PolarsResult;
+use polars_core::prelude::*;
+use pyo3_polars_derive::polars_expr;
+
+fn min_max_output(input_fields: &[Field]) -> PolarsResult<Field> {
+    let dtype = input_fields[0].dtype().clone();
+    let fields = vec![
+        Field::new("min".into(), dtype.clone()),
+        Field::new("max".into(), dtype),
+    ];
+    Ok(Field::new(
+        input_fields[0].name().clone(),
+        DataType::Struct(fields),
+    ))
+}
+
+#[polars_expr(output_type_func=min_max_output)]
+fn min_max(inputs: &[Series]) -> PolarsResult<DataFrame> {
+    let s = &inputs[0];
+    DataFrame::new(vec![
+        s.min_reduce()?.into_series("min".into()).into(),
+        s.max_reduce()?.into_series("max".into()).into(),
+    ])
+}
+
+fn main() {}
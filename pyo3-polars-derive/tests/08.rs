@@ -0,0 +1,25 @@
+use polars_core::error::{polars_bail, PolarsResult};
+use polars_core::prelude::{DataType, Field, Series};
+use polars_plan::dsl::FieldsMapper;
+use pyo3_polars::derive::{DefaultKwargs, DefaultKwargsExt};
+use pyo3_polars_derive::polars_expr;
+
+fn target_dtype(kwargs: &DefaultKwargs) -> PolarsResult<DataType> {
+    let target: String = kwargs.get_required("target")?;
+    match target.as_str() {
+        "f32" => Ok(DataType::Float32),
+        "f64" => Ok(DataType::Float64),
+        _ => polars_bail!(ComputeError: "unknown target '{}'", target),
+    }
+}
+
+fn cast_output(input_fields: &[Field], kwargs: DefaultKwargs) -> PolarsResult<Field> {
+    FieldsMapper::new(input_fields).with_dtype(target_dtype(&kwargs)?)
+}
+
+#[polars_expr(output_type_func_with_kwargs=cast_output)]
+fn cast_to_target(inputs: &[Series], kwargs: DefaultKwargs) -> PolarsResult<Series> {
+    inputs[0].cast(&target_dtype(&kwargs)?)
+}
+
+fn main() {}
@@ -0,0 +1,20 @@
+use polars_core::error::PolarsResult;
+use polars_core::prelude::{Field, Series};
+use polars_plan::dsl::FieldsMapper;
+use pyo3_polars_derive::polars_expr;
+
+fn same_output_type(input_fields: &[Field]) -> PolarsResult<Field> {
+    FieldsMapper::new(input_fields).map_to_supertype()
+}
+
+#[polars_expr(output_type_func=same_output_type, rename=false)]
+fn keep_name(series: &[Series]) -> PolarsResult<Series> {
+    Ok(series[0].clone().with_name("other".into()))
+}
+
+#[polars_expr(output_type=Int64, rename=false)]
+fn keep_name_with_dtype(series: &[Series]) -> PolarsResult<Series> {
+    Ok(series[0].clone().with_name("other".into()))
+}
+
+fn main() {}
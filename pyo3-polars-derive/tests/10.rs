@@ -0,0 +1,21 @@
+use polars_core::error::PolarsResult;
+use polars_core::prelude::Series;
+use pyo3_polars::derive::DefaultKwargsExt;
+use pyo3_polars_derive::polars_expr;
+
+#[polars_expr(output_type=Int64, kwargs_format="json")]
+fn json_kwargs(inputs: &[Series], kwargs: serde_json::Value) -> PolarsResult<Series> {
+    let offset: i64 = kwargs.get_required("offset")?;
+    Ok(&inputs[0] + offset)
+}
+
+#[polars_expr(output_type=Int64, kwargs_format="pickle")]
+fn pickle_kwargs(
+    inputs: &[Series],
+    kwargs: pyo3_polars::derive::DefaultKwargs,
+) -> PolarsResult<Series> {
+    let offset: i64 = kwargs.get_required("offset")?;
+    Ok(&inputs[0] + offset)
+}
+
+fn main() {}
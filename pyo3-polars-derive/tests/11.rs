@@ -0,0 +1,38 @@
+use polars_core::error::PolarsResult;
+use polars_core::prelude::*;
+use pyo3_polars_derive::polars_expr;
+
+mod ffi;
+
+#[polars_expr(output_type=Int64, is_elementwise)]
+fn add_one(inputs: &[Series]) -> PolarsResult<Series> {
+    Ok(&inputs[0] + 1)
+}
+
+#[polars_expr(output_type=Int64, is_elementwise, rename=false)]
+fn add_two(inputs: &[Series]) -> PolarsResult<Series> {
+    Ok(&inputs[0] + 2)
+}
+
+#[polars_expr(output_type=Int64, is_elementwise)]
+fn drop_first(inputs: &[Series]) -> PolarsResult<Series> {
+    Ok(inputs[0].slice(1, usize::MAX))
+}
+
+fn main() {
+    let s = Series::new("a".into(), [1i64, 2, 3]);
+
+    let out = ffi::call(_polars_plugin_add_one, &[s.clone()]).unwrap();
+    assert!(out.equals(&Series::new("a".into(), [2i64, 3, 4])));
+
+    // The length is only checked in debug builds.
+    let out = ffi::call(_polars_plugin_drop_first, &[s]);
+    if cfg!(debug_assertions) {
+        assert_eq!(
+            out.unwrap_err(),
+            "plugin function 'drop_first' is registered with 'is_elementwise', but returned 2 values for inputs of length 3"
+        );
+    } else {
+        assert_eq!(out.unwrap().len(), 2);
+    }
+}
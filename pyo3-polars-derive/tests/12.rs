@@ -0,0 +1,18 @@
+use polars_core::error::PolarsResult;
+use polars_core::prelude::*;
+use pyo3_polars_derive::polars_expr;
+
+#[polars_expr(output_type=Int64, in_place)]
+fn add_one(inputs: &mut [Series]) -> PolarsResult<()> {
+    let mut ca = std::mem::take(&mut inputs[0]).i64()?.clone();
+    ca.apply_mut(|v| v + 1);
+    inputs[0] = ca.into_series();
+    Ok(())
+}
+
+#[polars_expr(output_type=Int64, in_place)]
+fn reverse(inputs: &mut [Series]) {
+    inputs[0] = inputs[0].reverse();
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use pyo3_polars_derive::polars_expr;
+
+#[polars_expr(output_type=Int64, rename=true)]
+fn may_rename(
+    series: &[polars_core::prelude::Series],
+) -> polars_core::error::PolarsResult<polars_core::prelude::Series> {
+    Ok(series[0].clone().with_name("other".into()))
+}
+
+fn main() {}
@@ -0,0 +1,41 @@
+use polars_core::prelude::*;
+use polars_ffi::version_0::{export_series, import_series, CallerContext, SeriesExport};
+use std::ffi::CStr;
+use std::mem::ManuallyDrop;
+
+pub type PluginFunction = unsafe extern "C" fn(
+    *mut SeriesExport,
+    usize,
+    *const u8,
+    usize,
+    *mut SeriesExport,
+    *mut CallerContext,
+);
+
+/// Calls the exported `_polars_plugin_<name>` function like polars does, without kwargs.
+///
+/// Returns the output, or the message of the last error if the function failed. The error
+/// message function is re-exported by the first `#[polars_expr]` of the test.
+pub fn call(function: PluginFunction, inputs: &[Series]) -> Result<Series, String> {
+    // The plugin takes ownership of the exported inputs.
+    let mut exports = ManuallyDrop::new(inputs.iter().map(export_series).collect::<Vec<_>>());
+    let kwargs: &[u8] = &[];
+    let mut out = SeriesExport::empty();
+    let mut context = CallerContext::default();
+    unsafe {
+        function(
+            exports.as_mut_ptr(),
+            exports.len(),
+            kwargs.as_ptr(),
+            kwargs.len(),
+            &mut out,
+            &mut context,
+        );
+        if out.is_null() {
+            let msg = CStr::from_ptr(crate::_polars_plugin_get_last_error_message());
+            Err(msg.to_str().unwrap().to_string())
+        } else {
+            Ok(import_series(out).unwrap())
+        }
+    }
+}
@@ -3,4 +3,15 @@ fn tests() {
     let t = trybuild::TestCases::new();
     t.pass("tests/01.rs");
     t.pass("tests/02.rs");
+    t.pass("tests/03.rs");
+    t.pass("tests/04.rs");
+    t.pass("tests/05.rs");
+    t.pass("tests/06.rs");
+    t.pass("tests/07.rs");
+    t.pass("tests/08.rs");
+    t.pass("tests/09.rs");
+    t.pass("tests/10.rs");
+    t.pass("tests/11.rs");
+    t.pass("tests/12.rs");
+    t.compile_fail("tests/13.rs");
 }
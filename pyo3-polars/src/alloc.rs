@@ -1,20 +1,31 @@
-use std::alloc::{GlobalAlloc, Layout, System};
+#[cfg(not(any(feature = "allocator-mimalloc", feature = "allocator-jemalloc")))]
+use std::alloc::System;
+use std::alloc::{GlobalAlloc, Layout};
 use std::ffi::c_char;
 
 use once_cell::race::OnceRef;
 use pyo3::ffi::{PyCapsule_Import, Py_IsInitialized};
 use pyo3::Python;
 
+// The allocator that is used if polars' allocator isn't available, selected by the
+// `allocator-*` features. If both are active mimalloc wins.
+#[cfg(feature = "allocator-mimalloc")]
+static FALLBACK: mimalloc::MiMalloc = mimalloc::MiMalloc;
+#[cfg(all(feature = "allocator-jemalloc", not(feature = "allocator-mimalloc")))]
+static FALLBACK: tikv_jemallocator::Jemalloc = tikv_jemallocator::Jemalloc;
+#[cfg(not(any(feature = "allocator-mimalloc", feature = "allocator-jemalloc")))]
+static FALLBACK: System = System;
+
 unsafe extern "C" fn fallback_alloc(size: usize, align: usize) -> *mut u8 {
-    System.alloc(Layout::from_size_align_unchecked(size, align))
+    FALLBACK.alloc(Layout::from_size_align_unchecked(size, align))
 }
 
 unsafe extern "C" fn fallback_dealloc(ptr: *mut u8, size: usize, align: usize) {
-    System.dealloc(ptr, Layout::from_size_align_unchecked(size, align))
+    FALLBACK.dealloc(ptr, Layout::from_size_align_unchecked(size, align))
 }
 
 unsafe extern "C" fn fallback_alloc_zeroed(size: usize, align: usize) -> *mut u8 {
-    System.alloc_zeroed(Layout::from_size_align_unchecked(size, align))
+    FALLBACK.alloc_zeroed(Layout::from_size_align_unchecked(size, align))
 }
 
 unsafe extern "C" fn fallback_realloc(
@@ -23,7 +34,7 @@ unsafe extern "C" fn fallback_realloc(
     align: usize,
     new_size: usize,
 ) -> *mut u8 {
-    System.realloc(
+    FALLBACK.realloc(
         ptr,
         Layout::from_size_align_unchecked(size, align),
         new_size,
@@ -59,7 +70,16 @@ static ALLOCATOR_CAPSULE_NAME: &[u8] = b"polars.polars._allocator\0";
 /// ```
 ///
 /// If the allocator capsule (`polars.polars._allocator`) is not available,
-/// this allocator fallbacks to [`std::alloc::System`].
+/// this allocator fallbacks to [`std::alloc::System`], or to the allocator selected with
+/// one of these cargo features:
+///
+/// - `allocator-mimalloc` uses mimalloc, which is fast for the many small, short lived
+///   allocations of polars workloads and builds everywhere, but needs a C compiler.
+/// - `allocator-jemalloc` uses jemalloc, which fragments less in long running processes, but
+///   doesn't build on Windows and makes the build noticeably slower.
+///
+/// This mostly matters for musl targets, where the system allocator is slow. Polars' own
+/// allocator is always preferred, so memory can be freed on either side of the boundary.
 pub struct PolarsAllocator(OnceRef<'static, AllocatorCapsule>);
 
 impl PolarsAllocator {
@@ -0,0 +1,302 @@
+use crate::error::PyPolarsErr;
+use crate::{PySeries, POLARS, SERIES};
+use polars_core::prelude::*;
+use pyo3::exceptions::PyTypeError;
+#[cfg(feature = "dtype-decimal")]
+use pyo3::exceptions::PyValueError;
+use pyo3::intern;
+use pyo3::prelude::*;
+#[cfg(feature = "dtype-decimal")]
+use pyo3::sync::GILOnceCell;
+use pyo3::types::{PyBool, PyBytes, PyFloat, PyInt, PyList, PyString};
+#[cfg(feature = "dtype-decimal")]
+use pyo3::types::{PyTuple, PyType};
+
+#[repr(transparent)]
+#[derive(Debug, Clone)]
+/// A wrapper around an [`AnyValue`] that can be converted to and from python with `pyo3`.
+///
+/// Python values are converted like python polars would convert them in a `Series`, e.g. a
+/// `datetime.date` becomes a `Date` and a `dict` a `Struct`. Converting to python gives the
+/// same objects as indexing a python polars `Series`.
+pub struct PyAnyValue<'a>(pub AnyValue<'a>);
+
+impl<'a> From<PyAnyValue<'a>> for AnyValue<'a> {
+    fn from(value: PyAnyValue<'a>) -> Self {
+        value.0
+    }
+}
+
+impl<'py> FromPyObject<'py> for PyAnyValue<'static> {
+    fn extract_bound(ob: &Bound<'py, PyAny>) -> PyResult<Self> {
+        let av = if ob.is_none() {
+            AnyValue::Null
+        } else if ob.is_instance_of::<PyBool>() {
+            AnyValue::Boolean(ob.extract()?)
+        } else if ob.is_instance_of::<PyInt>() {
+            match ob.extract::<i64>() {
+                Ok(v) => AnyValue::Int64(v),
+                Err(_) => AnyValue::UInt64(ob.extract()?),
+            }
+        } else if ob.is_instance_of::<PyFloat>() {
+            AnyValue::Float64(ob.extract()?)
+        } else if let Ok(v) = ob.downcast::<PyString>() {
+            AnyValue::StringOwned(v.to_cow()?.as_ref().into())
+        } else if let Ok(v) = ob.downcast::<PyBytes>() {
+            AnyValue::BinaryOwned(v.as_bytes().to_vec())
+        } else if is_decimal(ob)? {
+            decimal_from_python(ob)?
+        } else {
+            // Let polars convert other objects, e.g. dates, lists and dicts, so that
+            // they get the same dtype as in python polars.
+            return any_value_from_polars(ob).map_err(|_| match ob.get_type().qualname() {
+                Ok(name) => PyTypeError::new_err(format!(
+                    "cannot convert object of type '{name}' to a polars value",
+                )),
+                Err(e) => e,
+            });
+        };
+        Ok(PyAnyValue(av))
+    }
+}
+
+/// Convert `ob` with python polars, by creating a series of length one.
+fn any_value_from_polars(ob: &Bound<'_, PyAny>) -> PyResult<PyAnyValue<'static>> {
+    let py = ob.py();
+    let s = SERIES
+        .bind(py)
+        .call1((PyString::new_bound(py, ""), PyList::new_bound(py, [ob])))?;
+    // Python polars falls back to `Object` for anything it doesn't understand.
+    let object = POLARS.bind(py).getattr(intern!(py, "Object"))?;
+    if s.getattr(intern!(py, "dtype"))?.eq(object)? {
+        return Err(PyTypeError::new_err("not a polars value"));
+    }
+    let s = s.extract::<PySeries>()?.0;
+    let av = s.get(0).map_err(PyPolarsErr::from)?.into_static();
+    Ok(PyAnyValue(av))
+}
+
+impl PyAnyValue<'_> {
+    /// Convert to a python object.
+    ///
+    /// Unlike [`IntoPy::into_py`] this raises a python exception instead of panicking, e.g. if
+    /// python polars can't convert a logical value.
+    pub fn try_into_py(self, py: Python<'_>) -> PyResult<PyObject> {
+        let out = match self.0 {
+            AnyValue::Null => py.None(),
+            AnyValue::Boolean(v) => v.into_py(py),
+            AnyValue::Int8(v) => v.into_py(py),
+            AnyValue::Int16(v) => v.into_py(py),
+            AnyValue::Int32(v) => v.into_py(py),
+            AnyValue::Int64(v) => v.into_py(py),
+            AnyValue::UInt8(v) => v.into_py(py),
+            AnyValue::UInt16(v) => v.into_py(py),
+            AnyValue::UInt32(v) => v.into_py(py),
+            AnyValue::UInt64(v) => v.into_py(py),
+            AnyValue::Float32(v) => v.into_py(py),
+            AnyValue::Float64(v) => v.into_py(py),
+            AnyValue::String(v) => v.into_py(py),
+            AnyValue::StringOwned(v) => v.as_str().into_py(py),
+            AnyValue::Binary(v) => PyBytes::new_bound(py, v).into_py(py),
+            AnyValue::BinaryOwned(v) => PyBytes::new_bound(py, &v).into_py(py),
+            #[cfg(feature = "dtype-decimal")]
+            AnyValue::Decimal(v, scale) => decimal_to_python(py, v, scale)?,
+            AnyValue::List(s) => {
+                let values = (0..s.len())
+                    .map(|i| {
+                        let av = s.get(i).map_err(PyPolarsErr::from)?;
+                        PyAnyValue(av).try_into_py(py)
+                    })
+                    .collect::<PyResult<Vec<_>>>()?;
+                PyList::new_bound(py, values).into_py(py)
+            }
+            av => {
+                // Let polars build the python object, so that logical types like dates and
+                // structs get the same representation as in python polars.
+                let s = Series::from_any_values(PlSmallStr::EMPTY, &[av], true)
+                    .map_err(PyPolarsErr::from)?;
+                PySeries(s)
+                    .try_into_py(py)?
+                    .call_method1(py, intern!(py, "__getitem__"), (0,))?
+            }
+        };
+        Ok(out)
+    }
+}
+
+impl IntoPy<PyObject> for PyAnyValue<'_> {
+    fn into_py(self, py: Python<'_>) -> PyObject {
+        self.try_into_py(py)
+            .expect("could not convert the value to python, use `PyAnyValue::try_into_py` to handle this error")
+    }
+}
+
+/// The maximum number of digits of a decimal, polars stores decimals as 128 bit integers.
+#[cfg(feature = "dtype-decimal")]
+const MAX_DECIMAL_DIGITS: usize = 38;
+
+#[cfg(feature = "dtype-decimal")]
+static DECIMAL: GILOnceCell<Py<PyType>> = GILOnceCell::new();
+
+#[cfg(feature = "dtype-decimal")]
+fn decimal_type(py: Python<'_>) -> PyResult<&Bound<'_, PyType>> {
+    DECIMAL
+        .get_or_try_init(py, || {
+            let decimal = py.import_bound("decimal")?.getattr("Decimal")?;
+            Ok::<_, PyErr>(decimal.downcast_into::<PyType>()?.unbind())
+        })
+        .map(|decimal| decimal.bind(py))
+}
+
+#[cfg(feature = "dtype-decimal")]
+fn is_decimal(ob: &Bound<'_, PyAny>) -> PyResult<bool> {
+    ob.is_instance(decimal_type(ob.py())?)
+}
+
+#[cfg(not(feature = "dtype-decimal"))]
+fn is_decimal(_ob: &Bound<'_, PyAny>) -> PyResult<bool> {
+    Ok(false)
+}
+
+/// Convert a python `decimal.Decimal`, erroring if it doesn't fit in a polars decimal.
+#[cfg(feature = "dtype-decimal")]
+fn decimal_from_python(ob: &Bound<'_, PyAny>) -> PyResult<AnyValue<'static>> {
+    let (sign, digits, exponent): (u8, Vec<u8>, Bound<PyAny>) =
+        ob.call_method0(intern!(ob.py(), "as_tuple"))?.extract()?;
+    // The exponent of `NaN` and infinity is a string.
+    let Ok(exponent) = exponent.extract::<i64>() else {
+        return Err(PyValueError::new_err(format!(
+            "cannot convert the non-finite decimal {ob} to a polars decimal"
+        )));
+    };
+    let Some((v, scale)) = abs_decimal_from_digits(&digits, exponent) else {
+        return Err(PyValueError::new_err(format!(
+            "cannot convert the decimal {ob} to a polars decimal: it needs more than \
+            {MAX_DECIMAL_DIGITS} digits, which don't fit in the 128 bit integers polars uses"
+        )));
+    };
+    let v = if sign == 1 { -v } else { v };
+    Ok(AnyValue::Decimal(v, scale))
+}
+
+/// Convert a polars decimal to a python `decimal.Decimal`.
+#[cfg(feature = "dtype-decimal")]
+fn decimal_to_python(py: Python<'_>, v: i128, scale: usize) -> PyResult<PyObject> {
+    let sign = u8::from(v < 0);
+    let digits = PyTuple::new_bound(py, decimal_to_digits(v));
+    let exponent = -(scale as i64);
+    let decimal = decimal_type(py)?.call1(((sign, digits, exponent),))?;
+    Ok(decimal.unbind())
+}
+
+/// The absolute value of the decimal with `digits` (most significant first) and `exponent`, like
+/// the parts of python's `Decimal.as_tuple()`, as an integer and a scale.
+///
+/// Polars doesn't support negative scales, so a positive exponent is applied to the integer.
+/// Returns `None` if the value needs more than [`MAX_DECIMAL_DIGITS`] digits.
+#[cfg(feature = "dtype-decimal")]
+fn abs_decimal_from_digits(digits: &[u8], exponent: i64) -> Option<(i128, usize)> {
+    let mut v = 0i128;
+    for &digit in digits {
+        debug_assert!(digit < 10, "invalid decimal digit {digit}");
+        v = v.checked_mul(10)?.checked_add(i128::from(digit))?;
+    }
+    let scale = if exponent > 0 {
+        if v != 0 {
+            let factor = 10i128.checked_pow(u32::try_from(exponent).ok()?)?;
+            v = v.checked_mul(factor)?;
+        }
+        0
+    } else {
+        usize::try_from(exponent.unsigned_abs()).ok()?
+    };
+    let max = 10i128.pow(MAX_DECIMAL_DIGITS as u32) - 1;
+    (v <= max && scale <= MAX_DECIMAL_DIGITS).then_some((v, scale))
+}
+
+/// The digits of the absolute value of `v`, most significant first.
+#[cfg(feature = "dtype-decimal")]
+fn decimal_to_digits(v: i128) -> Vec<u8> {
+    let digits = v
+        .unsigned_abs()
+        .to_string()
+        .bytes()
+        .map(|b| b - b'0')
+        .collect::<Vec<_>>();
+    // `u128::MAX` has 39 digits.
+    debug_assert!(digits.len() <= 39);
+    digits
+}
+
+#[cfg(all(test, feature = "dtype-decimal"))]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    const MAX_DECIMAL: i128 = 10i128.pow(MAX_DECIMAL_DIGITS as u32) - 1;
+
+    proptest! {
+        #[test]
+        fn decimal_digits_round_trip(
+            v in -MAX_DECIMAL..=MAX_DECIMAL,
+            scale in 0..=MAX_DECIMAL_DIGITS,
+        ) {
+            let digits = decimal_to_digits(v);
+            prop_assert!(digits.len() <= MAX_DECIMAL_DIGITS);
+            prop_assert_eq!(
+                abs_decimal_from_digits(&digits, -(scale as i64)),
+                Some((v.abs(), scale))
+            );
+        }
+
+        #[test]
+        fn decimal_digits_of_any_i128(v in any::<i128>()) {
+            let digits = decimal_to_digits(v);
+            let parsed = digits.iter().fold(0u128, |acc, &d| acc * 10 + u128::from(d));
+            prop_assert_eq!(parsed, v.unsigned_abs());
+            // Values with more than 38 digits don't fit in a decimal.
+            let abs = v.unsigned_abs();
+            let expected = (abs <= MAX_DECIMAL as u128).then_some((abs as i128, 0));
+            prop_assert_eq!(abs_decimal_from_digits(&digits, 0), expected);
+        }
+
+        #[test]
+        fn decimal_positive_exponent(v in 0..10i128.pow(19), exponent in 0..=19i64) {
+            let digits = decimal_to_digits(v);
+            prop_assert_eq!(
+                abs_decimal_from_digits(&digits, exponent),
+                Some((v * 10i128.pow(exponent as u32), 0))
+            );
+        }
+    }
+
+    #[test]
+    fn decimal_python_round_trip() {
+        Python::with_gil(|py| {
+            for (v, scale, repr) in [(-150, 2, "-1.50"), (0, 0, "0"), (7, 38, "7E-38")] {
+                let ob = PyAnyValue(AnyValue::Decimal(v, scale))
+                    .try_into_py(py)
+                    .unwrap()
+                    .into_bound(py);
+                assert_eq!(ob.str().unwrap().to_str().unwrap(), repr);
+                let av = ob.extract::<PyAnyValue>().unwrap().0;
+                assert_eq!(av, AnyValue::Decimal(v, scale));
+            }
+        });
+    }
+
+    #[test]
+    fn decimal_overflow() {
+        // 39 digits.
+        let digits = [9; MAX_DECIMAL_DIGITS + 1];
+        assert_eq!(abs_decimal_from_digits(&digits, 0), None);
+        assert_eq!(abs_decimal_from_digits(&[1; 50], -10), None);
+        assert_eq!(abs_decimal_from_digits(&[1], 38), None);
+        assert_eq!(
+            abs_decimal_from_digits(&[1], -(MAX_DECIMAL_DIGITS as i64) - 1),
+            None
+        );
+        assert_eq!(abs_decimal_from_digits(&[0], 100), Some((0, 0)));
+        assert_eq!(abs_decimal_from_digits(&[1], 37), Some((10i128.pow(37), 0)));
+    }
+}
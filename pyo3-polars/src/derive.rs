@@ -1,26 +1,171 @@
+use once_cell::sync::Lazy;
 use polars::prelude::PolarsError;
-use polars_core::error::{to_compute_err, PolarsResult};
+use polars_core::error::{polars_bail, polars_ensure, polars_err, to_compute_err, PolarsResult};
+use polars_core::prelude::{DataType, Series};
 pub use pyo3_polars_derive::polars_expr;
-use serde::Deserialize;
-use std::cell::RefCell;
+use serde::de::DeserializeOwned;
+use std::any::Any;
+use std::backtrace::{Backtrace, BacktraceStatus};
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
 use std::ffi::CString;
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Once, RwLock};
 
 /// Gives the caller extra information on how to execute the expression.
 pub use polars_ffi::version_0::CallerContext;
 
 /// A default opaque kwargs type.
+///
+/// This accepts any kwargs, use [`DefaultKwargsExt`] to read the values by key.
 pub type DefaultKwargs = serde_pickle::Value;
 
+/// Typed access to the values of [`DefaultKwargs`].
+///
+/// ```rust,ignore
+/// #[polars_expr(output_type=String)]
+/// fn pad(inputs: &[Series], kwargs: DefaultKwargs) -> PolarsResult<Series> {
+///     let width: usize = kwargs.get_required("width")?;
+///     let fill_char = kwargs.get_as::<char>("fill_char")?.unwrap_or(' ');
+///     // ...
+/// }
+/// ```
+pub trait DefaultKwargsExt {
+    /// Get the value of `key` as `T`, or `None` if the key is missing.
+    ///
+    /// Errors if the value can't be converted to `T`. A python `None` only converts to an
+    /// `Option<T>`.
+    fn get_as<T: DeserializeOwned>(&self, key: &str) -> PolarsResult<Option<T>>;
+
+    /// Get the value of `key` as `T`, erroring if the key is missing.
+    fn get_required<T: DeserializeOwned>(&self, key: &str) -> PolarsResult<T> {
+        self.get_as(key)?
+            .ok_or_else(|| polars_err!(ComputeError: "missing required kwarg '{}'", key))
+    }
+}
+
+impl DefaultKwargsExt for DefaultKwargs {
+    fn get_as<T: DeserializeOwned>(&self, key: &str) -> PolarsResult<Option<T>> {
+        let serde_pickle::Value::Dict(kwargs) = self else {
+            polars_bail!(ComputeError: "expected the kwargs to be a dict")
+        };
+        kwargs
+            .get(&serde_pickle::HashableValue::String(key.to_string()))
+            .map(|value| {
+                serde_pickle::from_value(value.clone())
+                    .map_err(|e| polars_err!(ComputeError: "invalid kwarg '{}': {}", key, e))
+            })
+            .transpose()
+    }
+}
+
+/// Read the values of JSON kwargs, see [`KwargsFormat::Json`].
+impl DefaultKwargsExt for serde_json::Value {
+    fn get_as<T: DeserializeOwned>(&self, key: &str) -> PolarsResult<Option<T>> {
+        let serde_json::Value::Object(kwargs) = self else {
+            polars_bail!(ComputeError: "expected the kwargs to be an object")
+        };
+        kwargs
+            .get(key)
+            .map(|value| {
+                serde_json::from_value(value.clone())
+                    .map_err(|e| polars_err!(ComputeError: "invalid kwarg '{}': {}", key, e))
+            })
+            .transpose()
+    }
+}
+
+/// Check that a plugin function got exactly the inputs of `signature`, by number and dtype.
+///
+/// Indexing the inputs of a plugin function that was called with the wrong number of arguments
+/// panics. This returns a `ComputeError` that names the expected signature instead, which can
+/// be caught in python.
+///
+/// The names in `signature` are the names of the parameters, e.g. of the python function that
+/// registers the plugin, and only label the inputs in the error. They aren't compared with the
+/// names of the input series, which are the names of whatever columns the caller passes.
+///
+/// ```rust,ignore
+/// #[polars_expr(output_type=Float64)]
+/// fn distance(inputs: &[Series]) -> PolarsResult<Series> {
+///     expect_inputs(inputs, &[("lat", DataType::Float64), ("lon", DataType::Float64)])?;
+///     // ...
+/// }
+/// ```
+pub fn expect_inputs(inputs: &[Series], signature: &[(&str, DataType)]) -> PolarsResult<()> {
+    let expected = || {
+        signature
+            .iter()
+            .map(|(name, dtype)| format!("{name}: {dtype}"))
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    polars_ensure!(
+        inputs.len() == signature.len(),
+        ComputeError: "expected {} inputs ({}), got {}", signature.len(), expected(), inputs.len()
+    );
+    for (i, (s, (name, dtype))) in inputs.iter().zip(signature).enumerate() {
+        polars_ensure!(
+            s.dtype() == dtype,
+            ComputeError: "expected input {} '{}' to be of dtype {}, got {}; the expected inputs are ({})",
+            i, name, dtype, s.dtype(), expected()
+        );
+    }
+    Ok(())
+}
+
 thread_local! {
     static LAST_ERROR: RefCell<CString> = RefCell::new(CString::default());
 }
 
-pub fn _parse_kwargs<'a, T>(kwargs: &'a [u8]) -> PolarsResult<T>
+/// Deserialize the kwargs that are passed to a plugin function.
+///
+/// The kwargs buffer is owned by the caller and only valid during the plugin call, so the
+/// kwargs type must own its data. Owned types like `String` or `Vec<u8>` compile:
+///
+/// ```rust
+/// # use polars_core::error::PolarsResult;
+/// let kwargs: PolarsResult<String> = pyo3_polars::derive::_parse_kwargs(&[]);
+/// ```
+///
+/// while types that borrow from the input are rejected at compile time. The call is the same as
+/// above, so this only fails because of the borrowed type:
+///
+/// ```rust,compile_fail
+/// # use polars_core::error::PolarsResult;
+/// let kwargs: PolarsResult<&str> = pyo3_polars::derive::_parse_kwargs(&[]);
+/// ```
+pub fn _parse_kwargs<T>(kwargs: &[u8]) -> PolarsResult<T>
 where
-    T: Deserialize<'a>,
+    T: DeserializeOwned,
 {
-    serde_pickle::from_slice(kwargs, Default::default()).map_err(to_compute_err)
+    _parse_kwargs_with_format(kwargs, KwargsFormat::Pickle)
+}
+
+/// The format the kwargs of a plugin function are serialized in, set with the `kwargs_format`
+/// attribute of `polars_expr`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum KwargsFormat {
+    /// The format polars serializes the kwargs of `register_plugin_function` in.
+    #[default]
+    Pickle,
+    /// A JSON object, e.g. for kwargs that are produced by another caller than polars.
+    Json,
+}
+
+/// Deserialize the kwargs that are passed to a plugin function from `format`.
+///
+/// See [`_parse_kwargs`] for the requirements on the kwargs type.
+pub fn _parse_kwargs_with_format<T>(kwargs: &[u8], format: KwargsFormat) -> PolarsResult<T>
+where
+    T: DeserializeOwned,
+{
+    match format {
+        KwargsFormat::Pickle => {
+            serde_pickle::from_slice(kwargs, Default::default()).map_err(to_compute_err)
+        }
+        KwargsFormat::Json => serde_json::from_slice(kwargs).map_err(to_compute_err),
+    }
 }
 
 pub fn _update_last_error(err: PolarsError) {
@@ -29,12 +174,99 @@ pub fn _update_last_error(err: PolarsError) {
     LAST_ERROR.with(|prev| *prev.borrow_mut() = msg)
 }
 
-pub fn _set_panic() {
-    let msg = "PANIC";
-    let msg = CString::new(msg).unwrap();
+/// Set the message of a panic in a plugin function, with the `payload` that was caught.
+///
+/// The message recorded by the panic hook includes the location of the panic. If the plugin
+/// replaced the panic hook, only the message of the payload is known.
+pub fn _set_panic(payload: &(dyn Any + Send)) {
+    let panic_msg = PANIC_MESSAGE
+        .with(|msg| msg.borrow_mut().take())
+        .unwrap_or_else(|| payload_message(payload).to_string());
+    let msg = format!("PANIC: {panic_msg}");
+    let msg = CString::new(msg.replace('\0', "")).unwrap();
     LAST_ERROR.with(|prev| *prev.borrow_mut() = msg)
 }
 
+/// The message of a panic, which is a `&str` or a `String` unless `panic_any` was used.
+fn payload_message(payload: &(dyn Any + Send)) -> &str {
+    payload
+        .downcast_ref::<&str>()
+        .copied()
+        .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+        .unwrap_or("Box<dyn Any>")
+}
+
+static QUIET_PANICS: AtomicBool = AtomicBool::new(true);
+static INSTALL_PANIC_HOOK: Once = Once::new();
+
+thread_local! {
+    static IN_PLUGIN_CALL: Cell<bool> = const { Cell::new(false) };
+    static PANIC_MESSAGE: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// Whether panics are printed to stderr, they are not by default.
+///
+/// A panic in a plugin function is raised as a python exception with the panic message, so
+/// printing it as well is usually just noise. Disable this, e.g. to get the backtrace while
+/// debugging a plugin; the output is the same as that of the default panic hook. Panics are
+/// also printed if `POLARS_VERBOSE=1` is set.
+pub fn set_quiet_panics(enabled: bool) {
+    QUIET_PANICS.store(enabled, Ordering::Relaxed);
+}
+
+/// Install the panic hook of the plugin.
+///
+/// The hook records the message of panics in plugin functions for [`_set_panic`], and prints
+/// panics to stderr unless [`set_quiet_panics`] is enabled and `POLARS_VERBOSE` isn't set.
+fn install_panic_hook() {
+    INSTALL_PANIC_HOOK.call_once(|| {
+        std::panic::set_hook(Box::new(|info| {
+            if IN_PLUGIN_CALL.with(Cell::get) {
+                let msg = payload_message(info.payload());
+                let msg = match info.location() {
+                    Some(location) => format!("{msg} at {location}"),
+                    None => msg.to_string(),
+                };
+                PANIC_MESSAGE.with(|prev| *prev.borrow_mut() = Some(msg));
+            }
+
+            let verbose = std::env::var("POLARS_VERBOSE").as_deref().unwrap_or("") == "1";
+            if verbose || !QUIET_PANICS.load(Ordering::Relaxed) {
+                let thread = std::thread::current();
+                let backtrace = Backtrace::capture();
+                eprintln!("thread '{}' {}", thread.name().unwrap_or("<unnamed>"), info);
+                match backtrace.status() {
+                    BacktraceStatus::Captured => eprintln!("stack backtrace:\n{backtrace}"),
+                    _ => eprintln!(
+                        "note: run with `RUST_BACKTRACE=1` environment variable to display a backtrace"
+                    ),
+                }
+            }
+        }));
+    });
+}
+
+/// Marks the current thread as running a plugin function while it is alive, so that the panic
+/// hook records the message of a panic.
+#[doc(hidden)]
+pub struct _PluginCallGuard {
+    was_in_plugin_call: bool,
+}
+
+impl _PluginCallGuard {
+    pub fn enter() -> Self {
+        install_panic_hook();
+        let was_in_plugin_call = IN_PLUGIN_CALL.with(|in_call| in_call.replace(true));
+        _PluginCallGuard { was_in_plugin_call }
+    }
+}
+
+impl Drop for _PluginCallGuard {
+    fn drop(&mut self) {
+        IN_PLUGIN_CALL.with(|in_call| in_call.set(self.was_in_plugin_call));
+    }
+}
+
 #[no_mangle]
 /// # Safety
 /// FFI function, so unsafe
@@ -42,16 +274,119 @@ pub unsafe extern "C" fn _polars_plugin_get_last_error_message() -> *const std::
     LAST_ERROR.with(|prev| prev.borrow_mut().as_ptr())
 }
 
+/// The progress handles that are created with [`new_progress`], by id.
+///
+/// The values are `f64` bits, so that updating them doesn't require a lock.
+static PROGRESS: Lazy<RwLock<HashMap<u64, Arc<AtomicU64>>>> = Lazy::new(Default::default);
+static NEXT_PROGRESS_ID: AtomicU64 = AtomicU64::new(1);
+
+/// The kwarg that passes the id of a progress handle to a plugin function.
+pub const PROGRESS_KWARG: &str = "_progress_id";
+
+/// A handle to report the progress of a long running plugin function.
+///
+/// It is passed as the last argument named `progress` when the function is
+/// annotated with `#[polars_expr(output_type=..., progress)]`. The progress is reported to the
+/// handle whose id is passed in the [`PROGRESS_KWARG`] kwarg, so that every call that is
+/// registered from python has its own progress. Without that kwarg updates are discarded.
+///
+/// Updating the progress is lock-free and doesn't acquire the GIL, so it is cheap to call
+/// from the hot loop of an expression. The progress is set to `1.0` when the function returns.
+#[derive(Clone)]
+pub struct ProgressReporter(Arc<AtomicU64>);
+
+impl ProgressReporter {
+    #[doc(hidden)]
+    pub fn _new(kwargs: &[u8], format: KwargsFormat) -> Self {
+        let id = match format {
+            KwargsFormat::Pickle => _parse_kwargs_with_format::<DefaultKwargs>(kwargs, format)
+                .ok()
+                .and_then(|kwargs| kwargs.get_as::<u64>(PROGRESS_KWARG).ok().flatten()),
+            KwargsFormat::Json => _parse_kwargs_with_format::<serde_json::Value>(kwargs, format)
+                .ok()
+                .and_then(|kwargs| kwargs.get_as::<u64>(PROGRESS_KWARG).ok().flatten()),
+        };
+        let state = id.and_then(|id| PROGRESS.read().unwrap().get(&id).cloned());
+        let reporter =
+            ProgressReporter(state.unwrap_or_else(|| Arc::new(AtomicU64::new(0.0f64.to_bits()))));
+        reporter.update(0.0);
+        reporter
+    }
+
+    /// Report the fraction of work that is done. The value is clamped to `[0.0, 1.0]`.
+    pub fn update(&self, fraction: f64) {
+        let fraction = if fraction.is_nan() {
+            0.0
+        } else {
+            fraction.clamp(0.0, 1.0)
+        };
+        self.0.store(fraction.to_bits(), Ordering::Relaxed)
+    }
+
+    /// Report that `done` out of `total` units of work are finished.
+    pub fn update_count(&self, done: usize, total: usize) {
+        if total > 0 {
+            self.update(done as f64 / total as f64)
+        }
+    }
+
+    /// Mark the work as finished.
+    pub fn finish(&self) {
+        self.update(1.0)
+    }
+}
+
+/// Create a progress handle and return its id, to pass in the [`PROGRESS_KWARG`] kwarg.
+///
+/// The handle starts at `0.0` and lives until [`drop_progress`] is called.
+pub fn new_progress() -> u64 {
+    let id = NEXT_PROGRESS_ID.fetch_add(1, Ordering::Relaxed);
+    PROGRESS
+        .write()
+        .unwrap()
+        .insert(id, Arc::new(AtomicU64::new(0.0f64.to_bits())));
+    id
+}
+
+/// Get the last reported progress of the handle `id`, or `None` if there is no such handle.
+pub fn get_progress(id: u64) -> Option<f64> {
+    PROGRESS
+        .read()
+        .unwrap()
+        .get(&id)
+        .map(|state| f64::from_bits(state.load(Ordering::Relaxed)))
+}
+
+/// Remove the handle `id`. Functions that still report to it aren't affected.
+pub fn drop_progress(id: u64) {
+    PROGRESS.write().unwrap().remove(&id);
+}
+
+/// Creates a progress handle, see [`new_progress`].
+///
+/// Python can call the `_polars_plugin_*_progress` functions of the shared library with
+/// `ctypes`, see `example/derive_expression`.
+#[no_mangle]
+pub extern "C" fn _polars_plugin_new_progress() -> u64 {
+    new_progress()
+}
+
+/// Returns the progress of the handle `id`, or a negative value if there is no such handle.
+#[no_mangle]
+pub extern "C" fn _polars_plugin_get_progress(id: u64) -> f64 {
+    get_progress(id).unwrap_or(-1.0)
+}
+
+/// Removes the progress handle `id`.
+#[no_mangle]
+pub extern "C" fn _polars_plugin_drop_progress(id: u64) {
+    drop_progress(id)
+}
+
 static INIT: AtomicBool = AtomicBool::new(false);
 
 fn start_up_init() {
-    // Set a custom panic hook that only shows output if verbose.
-    std::panic::set_hook(Box::new(|info| {
-        let show_message = std::env::var("POLARS_VERBOSE").as_deref().unwrap_or("") == "1";
-        if show_message {
-            eprintln!("{}", info)
-        }
-    }));
+    install_panic_hook();
 }
 
 #[no_mangle]
@@ -66,3 +401,138 @@ pub unsafe extern "C" fn _polars_plugin_get_version() -> u32 {
     // Stack bits together
     ((major as u32) << 16) + minor as u32
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use polars_core::prelude::NamedFrom;
+    use std::process::Command;
+
+    const PANIC_CHILD: &str = "PYO3_POLARS_TEST_PANIC_CHILD";
+
+    /// Panics in a plugin call, when run by [`panic_output`] in a child process.
+    #[test]
+    fn panic_output_child() {
+        let Ok(quiet) = std::env::var(PANIC_CHILD) else {
+            return;
+        };
+        set_quiet_panics(quiet == "1");
+        let _plugin_call = _PluginCallGuard::enter();
+        let payload =
+            std::panic::catch_unwind(|| panic!("plugin panicked for a test")).unwrap_err();
+        _set_panic(&*payload);
+
+        let msg = LAST_ERROR.with(|msg| msg.borrow().to_str().unwrap().to_string());
+        assert!(
+            msg.starts_with("PANIC: plugin panicked for a test at "),
+            "{msg}"
+        );
+        assert!(msg.contains("derive.rs"), "{msg}");
+    }
+
+    /// Runs [`panic_output_child`] in a child process and returns its stderr.
+    fn panic_stderr(quiet: bool) -> String {
+        let output = Command::new(std::env::current_exe().unwrap())
+            .args([
+                "--exact",
+                "derive::tests::panic_output_child",
+                "--nocapture",
+            ])
+            .env(PANIC_CHILD, if quiet { "1" } else { "0" })
+            .env_remove("POLARS_VERBOSE")
+            .output()
+            .unwrap();
+        let stderr = String::from_utf8(output.stderr).unwrap();
+        assert!(output.status.success(), "{stderr}");
+        stderr
+    }
+
+    fn progress_kwargs(id: u64) -> Vec<u8> {
+        let kwargs = HashMap::from([(PROGRESS_KWARG, id)]);
+        serde_pickle::to_vec(&kwargs, Default::default()).unwrap()
+    }
+
+    #[test]
+    fn progress_per_handle() {
+        let a = new_progress();
+        let b = new_progress();
+        let reporter = ProgressReporter::_new(&progress_kwargs(a), KwargsFormat::Pickle);
+        reporter.update_count(1, 4);
+        assert_eq!(get_progress(a), Some(0.25));
+        assert_eq!(get_progress(b), Some(0.0));
+
+        let other = ProgressReporter::_new(&progress_kwargs(b), KwargsFormat::Pickle);
+        other.update(f64::NAN);
+        reporter.finish();
+        assert_eq!(get_progress(a), Some(1.0));
+        assert_eq!(get_progress(b), Some(0.0));
+
+        drop_progress(a);
+        assert_eq!(get_progress(a), None);
+        assert_eq!(_polars_plugin_get_progress(a), -1.0);
+        drop_progress(b);
+    }
+
+    #[test]
+    fn progress_from_json_kwargs() {
+        let id = new_progress();
+        let kwargs = serde_json::to_vec(&HashMap::from([(PROGRESS_KWARG, id)])).unwrap();
+        ProgressReporter::_new(&kwargs, KwargsFormat::Json).update(0.5);
+        assert_eq!(get_progress(id), Some(0.5));
+        drop_progress(id);
+    }
+
+    #[test]
+    fn progress_without_handle() {
+        // Updates are discarded if the kwargs don't name a handle.
+        let reporter = ProgressReporter::_new(&[], KwargsFormat::Pickle);
+        reporter.finish();
+        let reporter = ProgressReporter::_new(&progress_kwargs(u64::MAX), KwargsFormat::Pickle);
+        reporter.finish();
+        assert_eq!(get_progress(u64::MAX), None);
+    }
+
+    #[test]
+    fn panic_output() {
+        let stderr = panic_stderr(false);
+        assert!(stderr.contains("plugin panicked for a test"), "{stderr}");
+        assert!(stderr.contains("derive.rs"), "{stderr}");
+
+        let stderr = panic_stderr(true);
+        assert!(!stderr.contains("plugin panicked for a test"), "{stderr}");
+    }
+
+    #[test]
+    fn expect_inputs_checks_number_and_dtypes() {
+        let signature = [("expr", DataType::String), ("other", DataType::Int64)];
+        let inputs = [
+            Series::new("a".into(), ["x"]),
+            Series::new("b".into(), [1i64]),
+        ];
+        expect_inputs(&inputs, &signature).unwrap();
+
+        let err = expect_inputs(&inputs[..1], &signature).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "expected 2 inputs (expr: str, other: i64), got 1"
+        );
+
+        let inputs = [
+            Series::new("a".into(), ["x"]),
+            Series::new("b".into(), [1i32]),
+        ];
+        let err = expect_inputs(&inputs, &signature).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "expected input 1 'other' to be of dtype i64, got i32; the expected inputs are \
+            (expr: str, other: i64)"
+        );
+    }
+
+    #[test]
+    fn expect_inputs_ignores_the_input_names() {
+        // The series are named after the columns the caller passes, not the parameters.
+        let inputs = [Series::new("other".into(), ["x"])];
+        expect_inputs(&inputs, &[("expr", DataType::String)]).unwrap();
+    }
+}
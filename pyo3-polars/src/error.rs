@@ -10,41 +10,83 @@ use thiserror::Error;
 pub enum PyPolarsErr {
     #[error(transparent)]
     Polars(#[from] PolarsError),
+    /// Raised as a `ValueError` in python.
+    #[error("{0}")]
+    Value(String),
+    /// Raised as a `DeserializationError` in python.
+    #[error("{0}")]
+    Deserialization(String),
+    /// Raised as a `RuntimeError` in python.
     #[error("{0}")]
     Other(String),
+    /// An exception raised in python, e.g. by a callback of the plugin.
+    ///
+    /// Raised as a `ComputeError` in python, with the original exception as its `__cause__`,
+    /// so that its traceback isn't lost.
+    #[error("{msg}")]
+    Python {
+        msg: String,
+        #[source]
+        cause: PyErr,
+    },
+}
+
+impl PyPolarsErr {
+    /// Wrap an exception raised in python, see [`PyPolarsErr::Python`].
+    pub fn from_py_err(err: PyErr) -> Self {
+        PyPolarsErr::Python {
+            msg: format!("error raised in python: {err}"),
+            cause: err,
+        }
+    }
 }
 
 impl std::convert::From<PyPolarsErr> for PyErr {
     fn from(err: PyPolarsErr) -> PyErr {
-        fn convert(err: &PolarsError) -> PyErr {
+        /// Raise `err` as the exception of its kind. A `Context` error is raised as the
+        /// exception of the error it wraps, with the full message that includes the context.
+        fn convert(err: &PolarsError, context: Option<String>) -> PyErr {
+            let msg = |inner: &dyn std::fmt::Display| match &context {
+                Some(context) => context.clone(),
+                None => inner.to_string(),
+            };
             match err {
-                PolarsError::ComputeError(err) => ComputeError::new_err(err.to_string()),
-                PolarsError::NoData(err) => NoDataError::new_err(err.to_string()),
-                PolarsError::ShapeMismatch(err) => ShapeError::new_err(err.to_string()),
-                PolarsError::SchemaMismatch(err) => SchemaError::new_err(err.to_string()),
-                PolarsError::IO { error, .. } => PyIOError::new_err(error.to_string()),
-                PolarsError::OutOfBounds(err) => PyIndexError::new_err(err.to_string()),
-                PolarsError::InvalidOperation(err) => PyValueError::new_err(err.to_string()),
-                PolarsError::Duplicate(err) => DuplicateError::new_err(err.to_string()),
-                PolarsError::ColumnNotFound(err) => ColumnNotFound::new_err(err.to_string()),
-                PolarsError::SchemaFieldNotFound(err) => {
-                    SchemaFieldNotFound::new_err(err.to_string())
-                }
-                PolarsError::StructFieldNotFound(err) => {
-                    StructFieldNotFound::new_err(err.to_string())
-                }
+                PolarsError::ComputeError(err) => ComputeError::new_err(msg(err)),
+                PolarsError::NoData(err) => NoDataError::new_err(msg(err)),
+                PolarsError::ShapeMismatch(err) => ShapeError::new_err(msg(err)),
+                PolarsError::SchemaMismatch(err) => SchemaError::new_err(msg(err)),
+                // The message of an IO error replaces the message of the underlying error.
+                PolarsError::IO { .. } => PyIOError::new_err(msg(err)),
+                PolarsError::OutOfBounds(err) => PyIndexError::new_err(msg(err)),
+                PolarsError::InvalidOperation(err) => PyValueError::new_err(msg(err)),
+                PolarsError::Duplicate(err) => DuplicateError::new_err(msg(err)),
+                PolarsError::ColumnNotFound(err) => ColumnNotFound::new_err(msg(err)),
+                PolarsError::SchemaFieldNotFound(err) => SchemaFieldNotFound::new_err(msg(err)),
+                PolarsError::StructFieldNotFound(err) => StructFieldNotFound::new_err(msg(err)),
                 PolarsError::StringCacheMismatch(err) => {
-                    StringCacheMismatchError::new_err(err.to_string())
+                    StringCacheMismatchError::new_err(msg(err))
+                }
+                PolarsError::SQLInterface(err) => SQLInterface::new_err(msg(err)),
+                PolarsError::SQLSyntax(err) => SQLSyntax::new_err(msg(err)),
+                PolarsError::Context { error, .. } => {
+                    convert(error, context.or_else(|| Some(err.to_string())))
                 }
-                PolarsError::SQLInterface(err) => SQLInterface::new_err(err.to_string()),
-                PolarsError::SQLSyntax(err) => SQLSyntax::new_err(err.to_string()),
-                PolarsError::Context { error, .. } => convert(error),
+                // Variants added by newer polars versions.
+                #[allow(unreachable_patterns)]
+                err => PyRuntimeError::new_err(format!("{err:?}")),
             }
         }
 
         use PyPolarsErr::*;
         match &err {
-            Polars(err) => convert(err),
+            Polars(err) => convert(err, None),
+            Value(err) => PyValueError::new_err(err.clone()),
+            Deserialization(err) => DeserializationError::new_err(err.clone()),
+            Python { msg, cause } => pyo3::Python::with_gil(|py| {
+                let err = ComputeError::new_err(msg.clone());
+                err.set_cause(py, Some(cause.clone_ref(py)));
+                err
+            }),
             _ => PyRuntimeError::new_err(format!("{:?}", &err)),
         }
     }
@@ -55,7 +97,10 @@ impl Debug for PyPolarsErr {
         use PyPolarsErr::*;
         match self {
             Polars(err) => write!(f, "{:?}", err),
+            Value(err) => write!(f, "ValueError: {:?}", err),
+            Deserialization(err) => write!(f, "DeserializationError: {:?}", err),
             Other(err) => write!(f, "BindingsError: {:?}", err),
+            Python { msg, .. } => write!(f, "PythonError: {:?}", msg),
         }
     }
 }
@@ -71,3 +116,32 @@ create_exception!(exceptions, DuplicateError, PyException);
 create_exception!(exceptions, StringCacheMismatchError, PyException);
 create_exception!(exceptions, SQLInterface, PyException);
 create_exception!(exceptions, SQLSyntax, PyException);
+create_exception!(exceptions, DeserializationError, PyException);
+
+/// Add the exceptions that [`PyPolarsErr`] is raised as to the python module `m`, so that
+/// python code can catch them, e.g. with `except my_plugin.ComputeError`.
+///
+/// The exceptions are added under their class names. They are the same classes that are
+/// raised, so catching them works regardless of the module they are imported from.
+pub fn register_exceptions(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    macro_rules! register {
+        ($($exception:ident),*) => {
+            $(m.add(stringify!($exception), m.py().get_type_bound::<$exception>())?;)*
+        };
+    }
+    register!(
+        ColumnNotFound,
+        SchemaFieldNotFound,
+        StructFieldNotFound,
+        ComputeError,
+        NoDataError,
+        ShapeError,
+        SchemaError,
+        DuplicateError,
+        StringCacheMismatchError,
+        SQLInterface,
+        SQLSyntax,
+        DeserializationError
+    );
+    Ok(())
+}
@@ -1,2 +1,31 @@
 pub(crate) mod to_py;
 pub(crate) mod to_rust;
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static FFI_TRACE: AtomicBool = AtomicBool::new(false);
+
+/// Log every conversion between rust and python to stderr.
+///
+/// Each conversion of a series logs which path was taken (python polars or pyarrow), the
+/// arrow compatibility level, the number of chunks and the dtype. This is meant for debugging
+/// interop issues, e.g. a mismatch between the polars version of the plugin and the one
+/// installed in python. When disabled, which is the default, tracing costs a single atomic load.
+pub fn set_ffi_trace(enabled: bool) {
+    FFI_TRACE.store(enabled, Ordering::Relaxed)
+}
+
+#[inline]
+pub(crate) fn ffi_trace_enabled() -> bool {
+    FFI_TRACE.load(Ordering::Relaxed)
+}
+
+/// `eprintln!` if [`set_ffi_trace`] is enabled.
+macro_rules! ffi_trace {
+    ($($arg:tt)*) => {
+        if $crate::ffi::ffi_trace_enabled() {
+            eprintln!("[pyo3-polars] {}", format_args!($($arg)*));
+        }
+    };
+}
+pub(crate) use ffi_trace;
@@ -1,28 +1,254 @@
+use super::to_rust::validate_pycapsule_name;
+use crate::error::PyPolarsErr;
+use crate::types::export_field;
+use crate::PyDataType;
+use polars::export::arrow::array::StructArray;
 use polars::export::arrow::ffi;
-use polars::prelude::{ArrayRef, ArrowField};
+use polars::prelude::*;
+use pyo3::exceptions::PyValueError;
 use pyo3::ffi::Py_uintptr_t;
 use pyo3::prelude::*;
+use pyo3::types::PyCapsule;
 
 /// Arrow array to Python.
+///
+/// The metadata of `field`, e.g. of an extension type, is exported with the array. Its dtype
+/// must be the dtype of `array`.
 pub(crate) fn to_py_array(
     array: ArrayRef,
+    field: &ArrowField,
     py: Python,
     pyarrow: Bound<'_, PyModule>,
 ) -> PyResult<PyObject> {
-    let schema = Box::new(ffi::export_field_to_c(&ArrowField::new(
-        "".into(),
-        array.dtype().clone(),
-        true,
-    )));
+    let schema = Box::new(ffi::export_field_to_c(field));
     let array = Box::new(ffi::export_array_to_c(array));
 
     let schema_ptr: *const ffi::ArrowSchema = &*schema;
     let array_ptr: *const ffi::ArrowArray = &*array;
 
     let array = pyarrow.getattr("Array")?.call_method1(
-        "_import_arrow_from_c",
+        "_import_from_c",
         (array_ptr as Py_uintptr_t, schema_ptr as Py_uintptr_t),
     )?;
 
     Ok(array.to_object(py))
 }
+
+/// Arrays that are exported to a consumer that imports them by pointer.
+///
+/// The arrays are boxed, so that their addresses are stable. A consumer that moves an array
+/// out marks our copy as released, as the C data interface requires, and dropping it is a
+/// no-op. Python polars reads the arrays without marking them, so after a successful import our
+/// copies must be forgotten with [`ExportedArrays::imported`]. If the import fails, dropping
+/// releases the arrays whose release callback is still set, i.e. that weren't taken.
+pub(crate) struct ExportedArrays(Vec<Box<ffi::ArrowArray>>);
+
+impl ExportedArrays {
+    pub(crate) fn with_capacity(capacity: usize) -> Self {
+        ExportedArrays(Vec::with_capacity(capacity))
+    }
+
+    /// Export `array` and return the pointer to pass to the consumer.
+    pub(crate) fn push(&mut self, array: ArrayRef) -> *const ffi::ArrowArray {
+        let array = Box::new(ffi::export_array_to_c(array));
+        let ptr: *const ffi::ArrowArray = &*array;
+        self.0.push(array);
+        ptr
+    }
+
+    /// The consumer took ownership of all arrays and will call their release callbacks.
+    pub(crate) fn imported(self) {
+        for array in self.0 {
+            std::mem::forget(*array);
+        }
+    }
+}
+
+/// Whether exporting `dtype` requires the arrow view types of [`CompatLevel::newest`].
+fn has_view_types(dtype: &ArrowDataType) -> bool {
+    match dtype {
+        ArrowDataType::Utf8View | ArrowDataType::BinaryView => true,
+        ArrowDataType::List(field)
+        | ArrowDataType::LargeList(field)
+        | ArrowDataType::FixedSizeList(field, _)
+        | ArrowDataType::Map(field, _) => has_view_types(&field.dtype),
+        ArrowDataType::Struct(fields) => fields.iter().any(|field| has_view_types(&field.dtype)),
+        ArrowDataType::Extension(ext) => has_view_types(&ext.inner),
+        _ => false,
+    }
+}
+
+/// Read the field of a `requested_schema` capsule of the Arrow PyCapsule Interface.
+fn import_requested_field(requested_schema: &Bound<PyAny>) -> PyResult<ArrowField> {
+    let capsule = requested_schema.downcast::<PyCapsule>()?;
+    validate_pycapsule_name(capsule, "arrow_schema")?;
+    // SAFETY: the capsule name guarantees that it points to an `ArrowSchema`, which we
+    // only read.
+    let field =
+        unsafe { ffi::import_field_from_c(&*(capsule.pointer() as *const ffi::ArrowSchema)) }
+            .map_err(PyPolarsErr::from)?;
+    Ok(field)
+}
+
+/// Export a frame as an arrow C stream of struct arrays, with one array per chunk.
+///
+/// If a `requested_schema` capsule is given, the columns of that schema are selected in its
+/// order and cast to its dtypes. A column that is missing or can't be cast raises an error.
+pub(crate) fn dataframe_to_stream(
+    mut df: DataFrame,
+    requested_schema: Option<&Bound<PyAny>>,
+) -> PyResult<ffi::ArrowArrayStream> {
+    let mut compat_level = CompatLevel::newest();
+    if let Some(requested_schema) = requested_schema {
+        let field = import_requested_field(requested_schema)?;
+        let ArrowDataType::Struct(fields) = field.dtype.to_logical_type() else {
+            return Err(PyValueError::new_err(format!(
+                "the requested schema of a frame must be a struct, got {:?}",
+                field.dtype
+            )));
+        };
+
+        let columns = fields
+            .iter()
+            .map(|field| {
+                let dtype = PyDataType::from_arrow_field(field)?.0;
+                let column = df.column(&field.name).map_err(PyPolarsErr::from)?;
+                let column = column.strict_cast(&dtype).map_err(|e| {
+                    e.wrap_msg(|msg| {
+                        format!(
+                            "cannot cast column '{}' to the requested dtype {}: {}",
+                            field.name, dtype, msg
+                        )
+                    })
+                });
+                Ok(column.map_err(PyPolarsErr::from)?)
+            })
+            .collect::<PyResult<Vec<_>>>()?;
+        df = DataFrame::new(columns).map_err(PyPolarsErr::from)?;
+
+        // Only hand out view types if the consumer asked for them.
+        if !fields.iter().any(|field| has_view_types(&field.dtype)) {
+            compat_level = CompatLevel::oldest();
+        }
+    }
+
+    df.align_chunks();
+    let fields = df
+        .get_columns()
+        .iter()
+        .map(|c| c.field().to_arrow(compat_level))
+        .collect::<Vec<_>>();
+    let dtype = ArrowDataType::Struct(fields);
+    let batches = (0..df.first_col_n_chunks())
+        .map(|i| {
+            let values = df
+                .get_columns()
+                .iter()
+                .map(|c| c.as_materialized_series().to_arrow(i, compat_level))
+                .collect::<Vec<_>>();
+            let length = values[0].len();
+            Ok(StructArray::new(dtype.clone(), length, values, None).boxed())
+        })
+        .collect::<Vec<_>>();
+
+    let field = ArrowField::new(PlSmallStr::EMPTY, dtype, false);
+    Ok(ffi::export_iterator(Box::new(batches.into_iter()), field))
+}
+
+/// An exported arrow schema that can be moved into a `PyCapsule`.
+#[repr(transparent)]
+pub(crate) struct ExportedSchema(ffi::ArrowSchema);
+
+// SAFETY: the exported schema owns all the data it points to.
+unsafe impl Send for ExportedSchema {}
+
+/// Export a series as a single arrow C array, concatenating its chunks.
+///
+/// If a `requested_schema` capsule is given, the series is cast to its dtype. A series that
+/// can't be cast raises an error.
+pub(crate) fn series_to_c_array(
+    mut s: Series,
+    requested_schema: Option<&Bound<PyAny>>,
+) -> PyResult<(ExportedSchema, ffi::ArrowArray)> {
+    let mut compat_level = CompatLevel::newest();
+    if let Some(requested_schema) = requested_schema {
+        let field = import_requested_field(requested_schema)?;
+        let dtype = PyDataType::from_arrow_field(&field)?.0;
+        let name = s.name().clone();
+        s = s.strict_cast(&dtype).map_err(|e| {
+            PyPolarsErr::from(e.wrap_msg(|msg| {
+                format!("cannot cast series '{name}' to the requested dtype {dtype}: {msg}")
+            }))
+        })?;
+
+        // Only hand out view types if the consumer asked for them.
+        if !has_view_types(&field.dtype) {
+            compat_level = CompatLevel::oldest();
+        }
+    }
+
+    let s = if s.n_chunks() == 0 {
+        Series::new_empty(s.name().clone(), s.dtype())
+    } else {
+        s.rechunk()
+    };
+    let array = s.to_arrow(0, compat_level);
+    let field = export_field(&s, &array, compat_level);
+    Ok((
+        ExportedSchema(ffi::export_field_to_c(&field)),
+        ffi::export_array_to_c(array),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use polars::export::arrow::array::PrimitiveArray;
+
+    #[test]
+    fn exported_arrays_are_released_if_not_taken() {
+        let taken = PrimitiveArray::from_vec(vec![1i64, 2, 3]);
+        let not_taken = PrimitiveArray::from_vec(vec![4i64, 5]);
+        let mut taken_values = taken.values().clone();
+        let mut not_taken_values = not_taken.values().clone();
+
+        let mut exported = ExportedArrays::with_capacity(2);
+        let taken_ptr = exported.push(taken.boxed());
+        exported.push(not_taken.boxed());
+        assert!(taken_values.get_mut_slice().is_none());
+        assert!(not_taken_values.get_mut_slice().is_none());
+
+        // The consumer moves the first array out and fails before it takes the second.
+        let taken = unsafe {
+            let array =
+                std::ptr::replace(taken_ptr as *mut ffi::ArrowArray, ffi::ArrowArray::empty());
+            ffi::import_array_from_c(array, ArrowDataType::Int64).unwrap()
+        };
+        drop(exported);
+        assert!(not_taken_values.get_mut_slice().is_some());
+        assert!(taken_values.get_mut_slice().is_none());
+
+        drop(taken);
+        assert!(taken_values.get_mut_slice().is_some());
+    }
+
+    #[test]
+    fn imported_arrays_are_released_by_the_consumer() {
+        let array = PrimitiveArray::from_vec(vec![1i64, 2, 3]);
+        let mut values = array.values().clone();
+
+        let mut exported = ExportedArrays::with_capacity(1);
+        let ptr = exported.push(array.boxed());
+
+        // Like python polars, the consumer reads the array without marking our copy.
+        let imported = unsafe {
+            let array = std::ptr::read_unaligned(ptr);
+            ffi::import_array_from_c(array, ArrowDataType::Int64).unwrap()
+        };
+        exported.imported();
+        assert!(values.get_mut_slice().is_none());
+
+        drop(imported);
+        assert!(values.get_mut_slice().is_some());
+    }
+}
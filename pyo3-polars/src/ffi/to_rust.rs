@@ -1,10 +1,21 @@
 use crate::error::PyPolarsErr;
+use polars::export::arrow::array::StructArray;
 use polars::export::arrow::ffi;
+use polars::export::arrow::ffi::ArrowArrayStreamReader;
 use polars::prelude::*;
+use pyo3::exceptions::PyValueError;
 use pyo3::ffi::Py_uintptr_t;
+use pyo3::intern;
 use pyo3::prelude::*;
+use pyo3::types::PyCapsule;
 
-pub fn array_to_rust(obj: &Bound<PyAny>) -> PyResult<ArrayRef> {
+/// A reader over an arrow C stream that was imported from python.
+///
+/// See [`arrow_stream_reader`].
+pub type ArrowStreamReader = ArrowArrayStreamReader<Box<ffi::ArrowArrayStream>>;
+
+/// Import a pyarrow array, together with its field so that metadata like extension types is kept.
+pub(crate) fn field_and_array_to_rust(obj: &Bound<PyAny>) -> PyResult<(ArrowField, ArrayRef)> {
     // prepare a pointer to receive the Array struct
     let array = Box::new(ffi::ArrowArray::empty());
     let schema = Box::new(ffi::ArrowSchema::empty());
@@ -21,7 +32,211 @@ pub fn array_to_rust(obj: &Bound<PyAny>) -> PyResult<ArrayRef> {
 
     unsafe {
         let field = ffi::import_field_from_c(schema.as_ref()).map_err(PyPolarsErr::from)?;
-        let array = ffi::import_array_from_c(*array, field.dtype).map_err(PyPolarsErr::from)?;
-        Ok(array)
+        let array =
+            ffi::import_array_from_c(*array, field.dtype.clone()).map_err(PyPolarsErr::from)?;
+        Ok((field, array))
+    }
+}
+
+/// Call `__arrow_c_stream__` on an object implementing the arrow PyCapsule interface.
+pub(crate) fn call_arrow_c_stream<'py>(obj: &Bound<'py, PyAny>) -> PyResult<Bound<'py, PyCapsule>> {
+    let capsule = obj.call_method0(intern!(obj.py(), "__arrow_c_stream__"))?;
+    Ok(capsule.downcast_into::<PyCapsule>()?)
+}
+
+pub(crate) fn validate_pycapsule_name(capsule: &Bound<PyCapsule>, expected: &str) -> PyResult<()> {
+    let name = capsule.name()?.map(|name| name.to_str()).transpose()?;
+    if name != Some(expected) {
+        return Err(PyValueError::new_err(format!(
+            "expected a PyCapsule with name '{expected}', got {name:?}"
+        )));
     }
+    Ok(())
+}
+
+/// Move the stream out of an `arrow_array_stream` capsule.
+pub(crate) fn import_stream_pycapsule(capsule: &Bound<PyCapsule>) -> PyResult<ArrowStreamReader> {
+    validate_pycapsule_name(capsule, "arrow_array_stream")?;
+
+    // SAFETY: the capsule name guarantees that it points to an `ArrowArrayStream`.
+    // We leave a released stream behind, so the destructor of the capsule is a no-op.
+    let stream = unsafe {
+        std::ptr::replace(
+            capsule.pointer() as *mut ffi::ArrowArrayStream,
+            ffi::ArrowArrayStream::empty(),
+        )
+    };
+    // SAFETY: the producer of the capsule must adhere to the C stream interface.
+    let reader =
+        unsafe { ArrowArrayStreamReader::try_new(Box::new(stream)) }.map_err(PyPolarsErr::from)?;
+    Ok(reader)
+}
+
+/// Import the arrays of an `arrow_schema` and an `arrow_array` capsule, as returned by
+/// `__arrow_c_array__`.
+pub(crate) fn import_array_pycapsules(
+    schema_capsule: &Bound<PyCapsule>,
+    array_capsule: &Bound<PyCapsule>,
+) -> PyResult<(ArrowField, ArrayRef)> {
+    validate_pycapsule_name(schema_capsule, "arrow_schema")?;
+    validate_pycapsule_name(array_capsule, "arrow_array")?;
+
+    // SAFETY: the capsule names guarantee that they point to an `ArrowSchema` and an
+    // `ArrowArray`. The schema is only borrowed. We leave a released array behind, so the
+    // destructor of the array capsule is a no-op.
+    unsafe {
+        let schema = &*(schema_capsule.pointer() as *const ffi::ArrowSchema);
+        let field = ffi::import_field_from_c(schema).map_err(PyPolarsErr::from)?;
+        let array = std::ptr::replace(
+            array_capsule.pointer() as *mut ffi::ArrowArray,
+            ffi::ArrowArray::empty(),
+        );
+        let array =
+            ffi::import_array_from_c(array, field.dtype.clone()).map_err(PyPolarsErr::from)?;
+        Ok((field, array))
+    }
+}
+
+/// Import all arrays of a stream, like the chunks of a pyarrow `ChunkedArray`.
+pub(crate) fn import_stream_as_chunks(
+    capsule: &Bound<PyCapsule>,
+) -> PyResult<(ArrowField, Vec<ArrayRef>)> {
+    let mut reader = import_stream_pycapsule(capsule)?;
+    let field = reader.field().clone();
+    let mut chunks = Vec::new();
+    // SAFETY: the producer of the capsule must adhere to the C stream interface.
+    while let Some(array) = unsafe { reader.next() } {
+        chunks.push(array.map_err(PyPolarsErr::from)?);
+    }
+    Ok((field, chunks))
+}
+
+/// Import a stream of struct arrays, like the record batches of a table, as a [`DataFrame`].
+///
+/// Every field of the struct becomes a column, with a chunk per batch. Nested struct fields
+/// stay struct columns. The validity of the top-level struct is ignored, as record batches
+/// don't have one.
+///
+/// With `checked` the columns are validated like [`DataFrame::new`], e.g. to reject duplicate
+/// names. Skip that only for streams of a genuine python polars `DataFrame`.
+pub(crate) fn import_stream_as_dataframe(
+    capsule: &Bound<PyCapsule>,
+    checked: bool,
+) -> PyResult<DataFrame> {
+    let mut reader = import_stream_pycapsule(capsule)?;
+    let ArrowDataType::Struct(fields) = reader.field().dtype.to_logical_type().clone() else {
+        return Err(PyPolarsErr::from(polars_err!(
+            ComputeError: "expected a stream of struct arrays, got {:?}", reader.field().dtype
+        ))
+        .into());
+    };
+
+    let mut chunks = vec![Vec::new(); fields.len()];
+    // SAFETY: the producer of the capsule must adhere to the C stream interface.
+    while let Some(array) = unsafe { reader.next() } {
+        let array = array.map_err(PyPolarsErr::from)?;
+        let array = array
+            .as_any()
+            .downcast_ref::<StructArray>()
+            .expect("struct dtype should be a struct array");
+        for (chunks, values) in chunks.iter_mut().zip(array.values()) {
+            chunks.push(values.clone());
+        }
+    }
+
+    columns_to_dataframe(&fields, chunks, checked)
+}
+
+/// Import an object with a `schema` and a `to_batches` method, like a pyarrow `Table`, as a
+/// [`DataFrame`], one array at a time.
+pub(crate) fn import_record_batches(obj: &Bound<PyAny>) -> PyResult<DataFrame> {
+    let py = obj.py();
+    let schema = obj.getattr(intern!(py, "schema"))?;
+    let fields = (0..schema.len()?)
+        .map(|i| field_to_rust(&schema.call_method1(intern!(py, "field"), (i,))?))
+        .collect::<PyResult<Vec<_>>>()?;
+
+    let mut chunks = vec![Vec::new(); fields.len()];
+    for batch in obj.call_method0(intern!(py, "to_batches"))?.iter()? {
+        let batch = batch?;
+        for (i, chunks) in chunks.iter_mut().enumerate() {
+            let column = batch.call_method1(intern!(py, "column"), (i,))?;
+            // The field of the schema is used, so that the names and metadata are kept.
+            let (_, array) = field_and_array_to_rust(&column)?;
+            chunks.push(array);
+        }
+    }
+    columns_to_dataframe(&fields, chunks, true)
+}
+
+/// Import a pyarrow `Field`.
+fn field_to_rust(obj: &Bound<PyAny>) -> PyResult<ArrowField> {
+    let schema = Box::new(ffi::ArrowSchema::empty());
+    let schema_ptr = &*schema as *const ffi::ArrowSchema;
+    obj.call_method1("_export_to_c", (schema_ptr as Py_uintptr_t,))?;
+    let field = unsafe { ffi::import_field_from_c(schema.as_ref()) }.map_err(PyPolarsErr::from)?;
+    Ok(field)
+}
+
+/// Build a [`DataFrame`] with a column per field, from the chunks of all batches.
+fn columns_to_dataframe(
+    fields: &[ArrowField],
+    chunks: Vec<Vec<ArrayRef>>,
+    checked: bool,
+) -> PyResult<DataFrame> {
+    let columns = fields
+        .iter()
+        .zip(chunks)
+        .map(|(field, chunks)| series_from_chunks(field, chunks).map(Column::from))
+        .collect::<PolarsResult<Vec<_>>>()
+        .map_err(PyPolarsErr::from)?;
+    let df = if checked {
+        DataFrame::new(columns).map_err(PyPolarsErr::from)?
+    } else {
+        // SAFETY: the fields of a struct array have the same length, and the caller
+        // guarantees that the names are unique.
+        unsafe { DataFrame::new_no_checks_height_from_first(columns) }
+    };
+    Ok(df)
+}
+
+/// Build a [`Series`] from the imported arrays of `field`.
+pub(crate) fn series_from_chunks(
+    field: &ArrowField,
+    chunks: Vec<ArrayRef>,
+) -> PolarsResult<Series> {
+    // Dictionary arrays are imported as `Categorical` columns, polars panics without them.
+    #[cfg(not(feature = "dtype-categorical"))]
+    polars_ensure!(
+        !matches!(field.dtype.to_logical_type(), ArrowDataType::Dictionary(..)),
+        ComputeError: "activate the `dtype-categorical` feature to import the dictionary encoded column '{}'",
+        field.name
+    );
+    // An empty stream has no chunks, but we still know the schema.
+    if chunks.is_empty() {
+        Ok(Series::new_empty(
+            field.name.clone(),
+            &DataType::from_arrow_field(field),
+        ))
+    } else {
+        Series::try_from((field, chunks))
+    }
+}
+
+/// Get the raw arrow stream of a python object that implements `__arrow_c_stream__`, like a
+/// pyarrow `Table` or `RecordBatchReader`.
+///
+/// This is an escape hatch for code that wants to consume arrow data without polars. The
+/// reader owns the stream: the python object doesn't have to outlive it, and the stream is
+/// released when the reader is dropped.
+///
+/// # Ownership and safety
+///
+/// This function is safe to call, but [`ArrowStreamReader::next`] is `unsafe`, as it trusts
+/// the producer to hand out arrays that follow the arrow C data interface. Producers may acquire
+/// the GIL while producing batches, so don't hold the GIL in another thread that waits on
+/// the reader.
+pub fn arrow_stream_reader(obj: &Bound<PyAny>) -> PyResult<ArrowStreamReader> {
+    let capsule = call_arrow_c_stream(obj)?;
+    import_stream_pycapsule(&capsule)
 }
@@ -0,0 +1,54 @@
+use crate::error::PyPolarsErr;
+use crate::PyDataFrame;
+use polars_core::prelude::*;
+use pyo3::prelude::*;
+
+type DataFrameBatches = Box<dyn Iterator<Item = PolarsResult<DataFrame>> + Send>;
+
+/// A python iterator over [`DataFrame`] batches that are produced by a rust iterator.
+///
+/// Return it from a `#[pyfunction]` to let python consume a large result batch by batch,
+/// instead of materializing it as a single frame. Each batch is produced when python asks for
+/// it, without holding the GIL, and converted to a python polars `DataFrame`. An error of a
+/// batch is raised by the `__next__` call that produced it.
+///
+/// ```rust,ignore
+/// #[pyfunction]
+/// fn batches(pydf: PyDataFrame, size: usize) -> PyDataFrameIter {
+///     let df = pydf.0;
+///     let offsets = (0..df.height()).step_by(size);
+///     PyDataFrameIter::new(offsets.map(move |offset| Ok(df.slice(offset as i64, size))))
+/// }
+/// ```
+#[pyclass(name = "DataFrameIter", module = "pyo3_polars")]
+pub struct PyDataFrameIter {
+    batches: DataFrameBatches,
+}
+
+impl PyDataFrameIter {
+    pub fn new<I>(batches: I) -> Self
+    where
+        I: IntoIterator<Item = PolarsResult<DataFrame>>,
+        I::IntoIter: Send + 'static,
+    {
+        PyDataFrameIter {
+            batches: Box::new(batches.into_iter()),
+        }
+    }
+}
+
+#[pymethods]
+impl PyDataFrameIter {
+    fn __iter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    fn __next__(&mut self, py: Python<'_>) -> PyResult<Option<PyObject>> {
+        let batches = &mut self.batches;
+        let Some(df) = py.allow_threads(|| batches.next()) else {
+            return Ok(None);
+        };
+        let df = df.map_err(PyPolarsErr::from)?;
+        PyDataFrame(df).try_into_py(py).map(Some)
+    }
+}
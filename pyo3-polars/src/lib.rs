@@ -42,22 +42,133 @@
 //! out_df = my_cool_function(df)
 //! ```
 mod alloc;
+mod any_value;
 #[cfg(feature = "derive")]
 pub mod derive;
 pub mod error;
 #[cfg(feature = "derive")]
 pub mod export;
 mod ffi;
+mod iter;
+#[cfg(feature = "lazy")]
+mod scan;
+#[cfg(feature = "dtype-categorical")]
+pub mod string_cache;
 mod types;
 
 pub use crate::alloc::PolarsAllocator;
+pub use crate::any_value::PyAnyValue;
+pub use crate::error::register_exceptions;
+pub use crate::ffi::set_ffi_trace;
+pub use crate::ffi::to_rust::{arrow_stream_reader, ArrowStreamReader};
+pub use crate::iter::PyDataFrameIter;
 use once_cell::sync::Lazy;
+#[cfg(feature = "dtype-categorical")]
+use polars::export::arrow::array::Utf8ViewArray;
+/// The arrow compatibility level that is used when data is exported to python.
+///
+/// - [`CompatLevel::oldest`] only uses arrow types that are understood by all arrow consumers,
+///   e.g. `large_string` instead of `string_view`.
+/// - [`CompatLevel::newest`] uses the newest arrow types polars supports, which are cheaper to export.
+/// - [`compat_level`] selects a level by number.
+///
+/// The levels known to this polars version are:
+///
+/// | level | arrow types |
+/// |-------|-------------|
+/// | `0`   | `large_string` and `large_binary` for strings and binary data |
+/// | `1`   | `string_view` and `binary_view` for strings and binary data |
+pub use polars_core::datatypes::CompatLevel;
+#[cfg(feature = "dtype-categorical")]
+use polars_core::error::{polars_bail, PolarsResult};
+#[cfg(feature = "dtype-categorical")]
+use polars_core::prelude::{DataType, PlHashSet, RevMapping};
 use pyo3::prelude::*;
+use pyo3::sync::GILOnceCell;
+#[cfg(feature = "dtype-categorical")]
+use std::sync::Arc;
 pub use types::*;
 
+/// Select a [`CompatLevel`] by number, where `0` is the oldest level.
+///
+/// A level newer than the newest level this polars version knows is clamped to
+/// [`CompatLevel::newest`], so code can ask for a level that only a newer polars supports.
+pub fn compat_level(level: u16) -> CompatLevel {
+    let newest = CompatLevel::newest().get_level();
+    CompatLevel::with_level(level.min(newest)).expect("level is within the supported range")
+}
+
+/// An `Enum` dtype with the given categories, in this order.
+///
+/// Use it to return an enum series, e.g. by casting a string series to it. The dtype is the same
+/// as `pl.Enum(categories)` in python.
+///
+/// Returns a `Duplicate` error if a category occurs more than once, like python polars.
+#[cfg(feature = "dtype-categorical")]
+pub fn enum_dtype(categories: &[&str]) -> PolarsResult<DataType> {
+    let mut seen = PlHashSet::with_capacity(categories.len());
+    if let Some(category) = categories.iter().find(|category| !seen.insert(**category)) {
+        polars_bail!(Duplicate: "enum categories must be unique, found duplicate '{}'", category);
+    }
+    let categories = Utf8ViewArray::from_slice_values(categories);
+    let rev_map = RevMapping::build_local(categories);
+    Ok(DataType::Enum(Some(Arc::new(rev_map)), Default::default()))
+}
+
 pub(crate) static POLARS: Lazy<PyObject> = Lazy::new(|| {
     Python::with_gil(|py| PyModule::import_bound(py, "polars").unwrap().to_object(py))
 });
 
 pub(crate) static SERIES: Lazy<PyObject> =
     Lazy::new(|| Python::with_gil(|py| POLARS.getattr(py, "Series").unwrap()));
+
+/// The result of importing `pyarrow`, which is an optional dependency.
+///
+/// The failed import is cached as well, so a missing pyarrow isn't looked up again on every
+/// conversion.
+static PYARROW: GILOnceCell<Result<Py<PyModule>, PyErr>> = GILOnceCell::new();
+
+/// The `pyarrow` module, or the error of importing it.
+pub(crate) fn pyarrow(py: Python<'_>) -> PyResult<Bound<'_, PyModule>> {
+    let pyarrow = PYARROW.get_or_init(py, || {
+        PyModule::import_bound(py, "pyarrow").map(Bound::unbind)
+    });
+    match pyarrow {
+        Ok(pyarrow) => Ok(pyarrow.bind(py).clone()),
+        Err(err) => Err(err.clone_ref(py)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compat_level_is_clamped() {
+        assert_eq!(
+            compat_level(0).get_level(),
+            CompatLevel::oldest().get_level()
+        );
+        let newest = CompatLevel::newest().get_level();
+        assert_eq!(compat_level(newest).get_level(), newest);
+        assert_eq!(compat_level(u16::MAX).get_level(), newest);
+    }
+
+    #[cfg(feature = "dtype-categorical")]
+    #[test]
+    fn enum_dtype_categories() {
+        use polars_core::error::PolarsError;
+
+        let dtype = enum_dtype(&["low", "mid", "high"]).unwrap();
+        let DataType::Enum(Some(rev_map), _) = &dtype else {
+            panic!("expected an enum, got {dtype:?}");
+        };
+        assert_eq!(rev_map.len(), 3);
+        assert_eq!(rev_map.get(1), "mid");
+        assert!(enum_dtype(&[]).is_ok());
+
+        let err = enum_dtype(&["low", "mid", "low"]).unwrap_err();
+        assert!(matches!(err, PolarsError::Duplicate(_)), "{err}");
+        assert!(err.to_string().contains("'low'"), "{err}");
+    }
+}
@@ -0,0 +1,143 @@
+use crate::error::PyPolarsErr;
+use crate::{PyDataFrame, PyDataFrameIter, PyExpr, PySchema};
+use polars_core::prelude::*;
+use polars_lazy::frame::LazyFrame;
+use polars_plan::dsl::col;
+use polars_plan::plans::{DslPlan, FileScan};
+use pyo3::prelude::*;
+use pyo3::types::{IntoPyDict, PyList};
+
+/// Whether `plan` reads from an [`AnonymousScan`](polars_plan::plans::AnonymousScan).
+///
+/// Anonymous scans are rust objects that can't be serialized, so such a plan can't be sent to
+/// python as is.
+pub(crate) fn has_anonymous_scan(plan: &DslPlan) -> bool {
+    match plan {
+        DslPlan::Scan {
+            scan_type: FileScan::Anonymous { .. },
+            ..
+        } => true,
+        DslPlan::Scan { .. } | DslPlan::DataFrameScan { .. } => false,
+        DslPlan::Filter { input, .. }
+        | DslPlan::Cache { input, .. }
+        | DslPlan::Select { input, .. }
+        | DslPlan::GroupBy { input, .. }
+        | DslPlan::HStack { input, .. }
+        | DslPlan::Distinct { input, .. }
+        | DslPlan::Sort { input, .. }
+        | DslPlan::Slice { input, .. }
+        | DslPlan::MapFunction { input, .. }
+        | DslPlan::Sink { input, .. } => has_anonymous_scan(input),
+        DslPlan::Join {
+            input_left,
+            input_right,
+            ..
+        } => has_anonymous_scan(input_left) || has_anonymous_scan(input_right),
+        DslPlan::Union { inputs, .. } | DslPlan::HConcat { inputs, .. } => {
+            inputs.iter().any(has_anonymous_scan)
+        }
+        DslPlan::ExtContext { input, contexts } => {
+            has_anonymous_scan(input) || contexts.iter().any(has_anonymous_scan)
+        }
+        DslPlan::IR { dsl, .. } => has_anonymous_scan(dsl),
+        // `PythonScan` only exists if another crate enables the `python` feature of
+        // polars-plan, it reads from python and has no inputs.
+        #[allow(unreachable_patterns)]
+        _ => false,
+    }
+}
+
+/// Register `lf` as a python polars io source, see `polars.io.plugins.register_io_source`.
+///
+/// The python `LazyFrame` that is returned only holds a handle to `lf`. When it is collected,
+/// polars passes the projection, the predicate and the slice of the python query to the
+/// source, which applies them to `lf` before it is collected in rust, so the optimizer can
+/// push them into the scans of `lf`.
+pub(crate) fn register_io_source(py: Python<'_>, mut lf: LazyFrame) -> PyResult<PyObject> {
+    let schema = lf.collect_schema().map_err(PyPolarsErr::from)?;
+    let source = RustScanSource { lf };
+    let kwargs = [
+        ("callable", source.into_py(py)),
+        ("schema", PySchema(schema).into_py(py)),
+    ]
+    .into_py_dict_bound(py);
+    let plugins = py.import_bound("polars.io.plugins")?;
+    let lf = plugins.call_method("register_io_source", (), Some(&kwargs))?;
+    Ok(lf.into_py(py))
+}
+
+/// The callable of a python io source that collects a rust [`LazyFrame`].
+#[pyclass(name = "RustScanSource", module = "pyo3_polars")]
+struct RustScanSource {
+    lf: LazyFrame,
+}
+
+#[pymethods]
+impl RustScanSource {
+    #[pyo3(signature = (with_columns, predicate, n_rows, batch_size))]
+    fn __call__(
+        &self,
+        py: Python<'_>,
+        with_columns: Option<Vec<String>>,
+        predicate: Option<Bound<'_, PyAny>>,
+        n_rows: Option<usize>,
+        batch_size: Option<usize>,
+    ) -> PyResult<PyObject> {
+        let mut lf = self.lf.clone();
+        // Polars doesn't filter the rows of an io source again, so the predicate must always
+        // be applied. If it can't be deserialized, e.g. because python polars is another
+        // version, it is applied in python after the whole plan is collected.
+        let predicate = match predicate.map(|p| p.extract::<PyExpr>().map_err(|_| p)) {
+            Some(Ok(predicate)) => {
+                lf = lf.filter(predicate.0);
+                None
+            }
+            Some(Err(predicate)) => Some(predicate),
+            None => None,
+        };
+
+        let Some(predicate) = predicate else {
+            if let Some(columns) = &with_columns {
+                lf = lf.select(
+                    columns
+                        .iter()
+                        .map(|name| col(name.as_str()))
+                        .collect::<Vec<_>>(),
+                );
+            }
+            if let Some(n_rows) = n_rows {
+                lf = lf.limit(n_rows as IdxSize);
+            }
+            let df = py
+                .allow_threads(|| lf.collect())
+                .map_err(PyPolarsErr::from)?;
+            return Ok(into_batches(df, batch_size).into_py(py));
+        };
+
+        let df = py
+            .allow_threads(|| lf.collect())
+            .map_err(PyPolarsErr::from)?;
+        let mut df = PyDataFrame(df)
+            .try_into_py(py)?
+            .into_bound(py)
+            .call_method1("filter", (predicate,))?;
+        if let Some(columns) = with_columns {
+            df = df.call_method1("select", (columns,))?;
+        }
+        if let Some(n_rows) = n_rows {
+            df = df.call_method1("head", (n_rows,))?;
+        }
+        Ok(PyList::new_bound(py, [df]).into_py(py))
+    }
+}
+
+/// Hand out `df` in slices of `batch_size` rows. An empty frame is handed out as is, so that
+/// python still sees its schema.
+fn into_batches(df: DataFrame, batch_size: Option<usize>) -> PyDataFrameIter {
+    if df.height() == 0 {
+        return PyDataFrameIter::new([Ok(df)]);
+    }
+    let batch_size = batch_size.filter(|&n| n > 0).unwrap_or(df.height());
+    let offsets = (0..df.height()).step_by(batch_size);
+    PyDataFrameIter::new(offsets.map(move |offset| Ok(df.slice(offset as i64, batch_size))))
+}
@@ -0,0 +1,29 @@
+//! Control the global string cache of polars from a plugin.
+//!
+//! Categorical columns that are created under the same string cache share their physical
+//! representation, which is required to combine them, e.g. to concatenate or join on them.
+//! Without it, such operations fail with a `StringCacheMismatchError`.
+//!
+//! # Interaction with python polars
+//!
+//! A compiled plugin links its own copy of polars. The string cache controlled by these
+//! functions is the one of the plugin and is independent of the state of
+//! `pl.enable_string_cache()` / `pl.StringCache()` in python. Categorical series are
+//! exchanged through arrow as dictionary arrays, so categories are re-encoded when they
+//! cross the boundary. Enable the cache in the plugin if it has to combine categoricals
+//! that are created or received separately, and enable it in python if python has to
+//! combine the categoricals a plugin returns.
+//!
+//! ```rust,ignore
+//! use pyo3_polars::string_cache::StringCacheHolder;
+//!
+//! fn process_categoricals() {
+//!     // The string cache is enabled until `_holder` is dropped.
+//!     let _holder = StringCacheHolder::hold();
+//!     // ...
+//! }
+//! ```
+
+pub use polars_core::{
+    disable_string_cache, enable_string_cache, using_string_cache, StringCacheHolder,
+};
@@ -1,24 +1,42 @@
 use super::*;
 use crate::error::PyPolarsErr;
-use crate::ffi::to_py::to_py_array;
+use crate::ffi::ffi_trace;
+use crate::ffi::to_py::{to_py_array, ExportedArrays};
 use polars::export::arrow;
+#[cfg(feature = "ipc")]
+use polars::prelude::IpcReader;
+#[cfg(feature = "parquet")]
+use polars::prelude::ParquetReader;
+#[cfg(any(feature = "ipc", feature = "parquet"))]
+use polars::prelude::SerReader;
+#[cfg(feature = "object")]
+use polars_core::chunked_array::object::registry::is_object_builder_registered;
 use polars_core::datatypes::{CompatLevel, DataType};
 use polars_core::prelude::*;
-use polars_core::utils::materialize_dyn_int;
+use polars_core::utils::{materialize_dyn_int, try_get_supertype};
 #[cfg(feature = "lazy")]
-use polars_lazy::frame::LazyFrame;
+use polars_lazy::frame::{IntoLazy, LazyFrame, ScanArgsAnonymous};
 #[cfg(feature = "lazy")]
+use polars_lazy::prelude::{JoinArgs, JoinType, UnionArgs};
+#[cfg(feature = "ops")]
+use polars_ops::series::SeriesMethods;
+#[cfg(feature = "lazy")]
+use polars_plan::dsl::col;
+#[cfg(feature = "expr")]
 use polars_plan::dsl::Expr;
 #[cfg(feature = "lazy")]
-use polars_plan::plans::DslPlan;
-use pyo3::exceptions::{PyTypeError, PyValueError};
+use polars_plan::plans::{AnonymousScan, DslPlan};
+#[cfg(feature = "rolling_window")]
+use polars_time::prelude::SeriesOpsTime;
+use pyo3::exceptions::{PyAssertionError, PyIndexError, PyRuntimeError, PyTypeError, PyValueError};
 use pyo3::ffi::Py_uintptr_t;
 use pyo3::intern;
 use pyo3::prelude::*;
 use pyo3::pybacked::PyBackedStr;
-use pyo3::types::PyDict;
-#[cfg(feature = "dtype-struct")]
-use pyo3::types::PyList;
+use pyo3::types::{IntoPyDict, PyCapsule, PyDict, PyList};
+use std::borrow::Cow;
+use std::ffi::CString;
+use std::ops::{Deref, DerefMut};
 
 #[cfg(feature = "dtype-categorical")]
 pub(crate) fn get_series(obj: &Bound<'_, PyAny>) -> PyResult<Series> {
@@ -34,6 +52,23 @@ pub struct PySeries(pub Series);
 #[repr(transparent)]
 #[derive(Debug, Clone)]
 /// A wrapper around a [`DataFrame`] that can be converted to and from python with `pyo3`.
+///
+/// To return extra python objects together with a frame, return a tuple. It converts to a
+/// python `tuple` like any other tuple of convertible values:
+///
+/// ```rust,ignore
+/// #[pyfunction]
+/// fn drop_nulls(py: Python<'_>, pydf: PyDataFrame) -> PyResult<(PyDataFrame, PyObject)> {
+///     let df = pydf.0.drop_nulls::<String>(None).map_err(PyPolarsErr::from)?;
+///     let meta = PyDict::new_bound(py);
+///     meta.set_item("n_dropped", pydf.0.height() - df.height())?;
+///     Ok((PyDataFrame(df), meta.into_py(py)))
+/// }
+/// ```
+///
+/// Extracting a frame with duplicate column names, e.g. a pyarrow `Table`, raises a
+/// `DuplicateError` that names the repeated column. Only [`PyDataFrame::extract_unchecked`]
+/// skips this check.
 pub struct PyDataFrame(pub DataFrame);
 
 #[cfg(feature = "lazy")]
@@ -43,14 +78,20 @@ pub struct PyDataFrame(pub DataFrame);
 /// # Warning
 /// If the [`LazyFrame`] contains in memory data,
 /// such as a [`DataFrame`] this will be serialized/deserialized.
+/// Only a plan that is nothing but a scan of a [`DataFrame`] is converted to python without
+/// copying, use [`PyLazyFrame::from_arrow_backed`] for the other direction.
 ///
 /// It is recommended to only have `LazyFrame`s that scan data
 /// from disk
 pub struct PyLazyFrame(pub LazyFrame);
 
-#[cfg(feature = "lazy")]
+#[cfg(feature = "expr")]
 #[repr(transparent)]
 #[derive(Clone)]
+/// A wrapper around an [`Expr`] that can be converted to and from python with `pyo3`.
+///
+/// This only needs the `expr` feature, so plugins that inspect or build expressions don't have
+/// to compile the lazy engine.
 pub struct PyExpr(pub Expr);
 
 #[repr(transparent)]
@@ -67,7 +108,14 @@ pub struct PyTimeUnit(TimeUnit);
 
 #[repr(transparent)]
 #[derive(Clone)]
-pub struct PyField(Field);
+/// A wrapper around a [`Field`] that can be converted to and from a python polars `Field`.
+pub struct PyField(pub Field);
+
+impl From<PyField> for Field {
+    fn from(value: PyField) -> Self {
+        value.0
+    }
+}
 
 impl<'py> FromPyObject<'py> for PyField {
     fn extract_bound(ob: &Bound<'py, PyAny>) -> PyResult<Self> {
@@ -84,7 +132,13 @@ impl<'py> FromPyObject<'py> for PyField {
 
 impl<'py> FromPyObject<'py> for PyTimeUnit {
     fn extract_bound(ob: &Bound<'py, PyAny>) -> PyResult<Self> {
-        let parsed = match &*ob.extract::<PyBackedStr>()? {
+        PyTimeUnit::parse(&ob.extract::<PyBackedStr>()?)
+    }
+}
+
+impl PyTimeUnit {
+    fn parse(time_unit: &str) -> PyResult<Self> {
+        let parsed = match time_unit {
             "ns" => TimeUnit::Nanoseconds,
             "us" => TimeUnit::Microseconds,
             "ms" => TimeUnit::Milliseconds,
@@ -159,45 +213,1473 @@ impl AsRef<Schema> for PySchema {
     }
 }
 
-impl<'a> FromPyObject<'a> for PySeries {
-    fn extract_bound(ob: &Bound<'a, PyAny>) -> PyResult<Self> {
-        let ob = ob.call_method0("rechunk")?;
-
-        let name = ob.getattr("name")?;
-        let py_name = name.str()?;
-        let name = py_name.to_cow()?;
+impl Deref for PySeries {
+    type Target = Series;
+
+    fn deref(&self) -> &Series {
+        &self.0
+    }
+}
+
+impl DerefMut for PySeries {
+    fn deref_mut(&mut self) -> &mut Series {
+        &mut self.0
+    }
+}
+
+impl Deref for PyDataFrame {
+    type Target = DataFrame;
+
+    fn deref(&self) -> &DataFrame {
+        &self.0
+    }
+}
+
+impl DerefMut for PyDataFrame {
+    fn deref_mut(&mut self) -> &mut DataFrame {
+        &mut self.0
+    }
+}
+
+impl PySeries {
+    /// Unwrap the [`Series`], like `Series::from(pyseries)`.
+    pub fn into_inner(self) -> Series {
+        self.0
+    }
+
+    /// The mapping from physical codes to categories of a `Categorical` or `Enum` series.
+    ///
+    /// Returns `None` for any other dtype. Use it to build an output in the same categorical
+    /// space as the input, without remapping the codes.
+    #[cfg(feature = "dtype-categorical")]
+    pub fn categorical_rev_map(&self) -> Option<Arc<RevMapping>> {
+        let ca = self.0.categorical().ok()?;
+        Some(ca.get_rev_map().clone())
+    }
+
+    /// Build a [`PySeries`] from a python sequence, like a `list` or a `range`.
+    ///
+    /// Unlike the [`FromPyObject`] implementation this doesn't require a polars `Series`.
+    /// The data type is inferred from the values, where mixed types are cast to their supertype
+    /// and `None` values become nulls. The resulting series has an empty name.
+    pub fn from_py_sequence(ob: &Bound<'_, PyAny>) -> PyResult<Self> {
+        let values = ob
+            .iter()?
+            .map(|v| v?.extract::<PyAnyValue>().map(|v| v.0))
+            .collect::<PyResult<Vec<_>>>()?;
+        let s = Series::from_any_values(PlSmallStr::EMPTY, &values, false)
+            .map_err(PyPolarsErr::from)?;
+        Ok(PySeries(s))
+    }
+
+    /// Extract a [`PySeries`] like the [`FromPyObject`] implementation, but turn the `NaN`
+    /// values of a float series into nulls, like `pl.Series(..., nan_to_null=True)`.
+    ///
+    /// The default extraction keeps `NaN` as is. Use this function for an argument that
+    /// should treat `NaN` as missing:
+    ///
+    /// ```rust,ignore
+    /// #[pyfunction]
+    /// fn mean(#[pyo3(from_py_with = "PySeries::extract_nan_to_null")] s: PySeries) -> Option<f64> {
+    ///     s.0.mean()
+    /// }
+    /// ```
+    pub fn extract_nan_to_null(ob: &Bound<'_, PyAny>) -> PyResult<PySeries> {
+        let s = ob.extract::<PySeries>()?.0;
+        let s = match s.dtype() {
+            DataType::Float32 => s
+                .f32()
+                .unwrap()
+                .apply(|v| v.filter(|v| !v.is_nan()))
+                .into_series(),
+            DataType::Float64 => s
+                .f64()
+                .unwrap()
+                .apply(|v| v.filter(|v| !v.is_nan()))
+                .into_series(),
+            _ => s,
+        };
+        Ok(PySeries(s))
+    }
+
+    /// Create a series of length `n` where every value is `value`.
+    ///
+    /// The dtype is inferred from the value, e.g. a python `int` becomes `Int64` and
+    /// `None` becomes `Null`.
+    pub fn new_full(name: &str, value: PyAnyValue<'_>, n: usize) -> PyResult<PySeries> {
+        let s = Series::from_any_values(name.into(), &[value.0], true)
+            .map_err(PyPolarsErr::from)?
+            .new_from_index(0, n);
+        Ok(PySeries(s))
+    }
+
+    /// Append `n` copies of `value` to the series.
+    ///
+    /// The value is cast to the dtype of the series, which raises an error if that isn't
+    /// possible instead of appending nulls.
+    pub fn extend_constant(&self, value: PyAnyValue<'_>, n: usize) -> PyResult<PySeries> {
+        let dtype = self.0.dtype();
+        let fill = Series::from_any_values(self.0.name().clone(), &[value.0], true)
+            .and_then(|s| s.strict_cast(dtype))
+            .map_err(|e| {
+                PyPolarsErr::from(e.wrap_msg(|msg| {
+                    format!("cannot extend a series of dtype {dtype} with this value: {msg}")
+                }))
+            })?
+            .new_from_index(0, n);
+        let mut out = self.0.clone();
+        out.append(&fill).map_err(PyPolarsErr::from)?;
+        Ok(PySeries(out))
+    }
+
+    /// Sort the series.
+    pub fn sort(&self, descending: bool, nulls_last: bool) -> PyResult<PySeries> {
+        let options = SortOptions::default()
+            .with_order_descending(descending)
+            .with_nulls_last(nulls_last);
+        let s = self.0.sort(options).map_err(PyPolarsErr::from)?;
+        Ok(PySeries(s))
+    }
+
+    /// Get the indices that would sort the series.
+    ///
+    /// The returned series has the index dtype of polars (`UInt32`, or `UInt64` with `bigidx`).
+    pub fn arg_sort(&self, descending: bool, nulls_last: bool) -> PySeries {
+        let options = SortOptions::default()
+            .with_order_descending(descending)
+            .with_nulls_last(nulls_last);
+        PySeries(self.0.arg_sort(options).into_series())
+    }
+
+    /// Convert the series to a pyarrow `Array`, without going through python polars.
+    ///
+    /// A series with multiple chunks becomes a pyarrow `ChunkedArray` with one array per
+    /// chunk. Pyarrow arrays have no name, so the name of the series is dropped. Data is
+    /// exported with [`CompatLevel::oldest`], so strings become `large_string`.
+    pub fn into_pyarrow(self, py: Python<'_>) -> PyResult<PyObject> {
+        let pyarrow = pyarrow(py)?;
+        let s = if self.0.n_chunks() == 0 {
+            self.0.rechunk()
+        } else {
+            self.0
+        };
+        let arrays = (0..s.n_chunks())
+            .map(|i| {
+                let array = s.to_arrow(i, CompatLevel::oldest());
+                let field = export_field(&s, &array, CompatLevel::oldest());
+                to_py_array(array, &field, py, pyarrow.clone())
+            })
+            .collect::<PyResult<Vec<_>>>()?;
+        if arrays.len() == 1 {
+            return Ok(arrays.into_iter().next().unwrap());
+        }
+        let out = pyarrow.call_method1(intern!(py, "chunked_array"), (arrays,))?;
+        Ok(out.into_py(py))
+    }
+
+    fn bool_operands<'a>(
+        &'a self,
+        other: &'a PySeries,
+    ) -> PyResult<(&'a BooleanChunked, &'a BooleanChunked)> {
+        let lhs = self.0.bool().map_err(PyPolarsErr::from)?;
+        let rhs = other.0.bool().map_err(PyPolarsErr::from)?;
+        if lhs.len() != rhs.len() && lhs.len() != 1 && rhs.len() != 1 {
+            return Err(PyPolarsErr::from(polars_err!(
+                ShapeMismatch: "cannot combine boolean series of length {} and {}", lhs.len(), rhs.len()
+            ))
+            .into());
+        }
+        Ok((lhs, rhs))
+    }
+
+    /// Boolean `and` with Kleene logic: `false & null` is `false` and `true & null` is `null`.
+    ///
+    /// Both series must be of dtype `Boolean`. Series of length 1 are broadcasted.
+    pub fn and_kleene(&self, other: &PySeries) -> PyResult<PySeries> {
+        let (lhs, rhs) = self.bool_operands(other)?;
+        Ok(PySeries((lhs & rhs).into_series()))
+    }
+
+    /// Boolean `or` with Kleene logic: `true | null` is `true` and `false | null` is `null`.
+    ///
+    /// Both series must be of dtype `Boolean`. Series of length 1 are broadcasted.
+    pub fn or_kleene(&self, other: &PySeries) -> PyResult<PySeries> {
+        let (lhs, rhs) = self.bool_operands(other)?;
+        Ok(PySeries((lhs | rhs).into_series()))
+    }
+
+    /// Get the unique values of the series.
+    ///
+    /// With `maintain_order` the values are returned in the order of their first occurrence,
+    /// otherwise the order is unspecified. Not every dtype supports this; nested dtypes like
+    /// lists of strings raise a `ValueError`.
+    pub fn unique(&self, maintain_order: bool) -> PyResult<PySeries> {
+        let s = if maintain_order {
+            self.0.unique_stable()
+        } else {
+            self.0.unique()
+        }
+        .map_err(PyPolarsErr::from)?;
+        Ok(PySeries(s))
+    }
+
+    /// Count the number of unique values, where `null` counts as a value.
+    ///
+    /// Like [`PySeries::unique`] this raises a `ValueError` for unsupported dtypes.
+    pub fn n_unique(&self) -> PyResult<usize> {
+        let n = self.0.n_unique().map_err(PyPolarsErr::from)?;
+        Ok(n)
+    }
+
+    /// Count the occurrences of the unique values.
+    ///
+    /// Returns a frame with the unique values in a column with the name of the series and
+    /// their counts in a column named `"count"`. If `sort` is set, the most frequent values
+    /// come first.
+    #[cfg(feature = "ops")]
+    pub fn value_counts(&self, sort: bool, parallel: bool) -> PyResult<PyDataFrame> {
+        let df = self
+            .0
+            .value_counts(sort, parallel, PlSmallStr::from_static("count"), false)
+            .map_err(PyPolarsErr::from)?;
+        Ok(PyDataFrame(df))
+    }
+
+    #[cfg(feature = "rolling_window")]
+    fn rolling_options(
+        &self,
+        window_size: usize,
+        min_periods: Option<usize>,
+        center: bool,
+    ) -> PyResult<RollingOptionsFixedWindow> {
+        let dtype = self.0.dtype();
+        if !(dtype.is_integer() || dtype.is_float()) {
+            return Err(PyPolarsErr::from(
+                polars_err!(InvalidOperation: "rolling aggregations require a numeric series, got dtype {}", dtype),
+            )
+            .into());
+        }
+        Ok(RollingOptionsFixedWindow {
+            window_size,
+            min_periods: min_periods.unwrap_or(window_size),
+            center,
+            ..Default::default()
+        })
+    }
+
+    /// Apply a rolling mean over windows of `window_size` values.
+    ///
+    /// Windows with fewer than `min_periods` non-null values are null, where `min_periods`
+    /// defaults to `window_size`. With `center` the window is centered on each value instead
+    /// of ending at it. The series must be numeric.
+    #[cfg(feature = "rolling_window")]
+    pub fn rolling_mean(
+        &self,
+        window_size: usize,
+        min_periods: Option<usize>,
+        center: bool,
+    ) -> PyResult<PySeries> {
+        let options = self.rolling_options(window_size, min_periods, center)?;
+        let s = self.0.rolling_mean(options).map_err(PyPolarsErr::from)?;
+        Ok(PySeries(s))
+    }
+
+    /// Apply a rolling sum over windows of `window_size` values.
+    ///
+    /// See [`PySeries::rolling_mean`] for the options.
+    #[cfg(feature = "rolling_window")]
+    pub fn rolling_sum(
+        &self,
+        window_size: usize,
+        min_periods: Option<usize>,
+        center: bool,
+    ) -> PyResult<PySeries> {
+        let options = self.rolling_options(window_size, min_periods, center)?;
+        let s = self.0.rolling_sum(options).map_err(PyPolarsErr::from)?;
+        Ok(PySeries(s))
+    }
+
+    /// Apply a rolling standard deviation (with `ddof = 1`) over windows of `window_size` values.
+    ///
+    /// See [`PySeries::rolling_mean`] for the options.
+    #[cfg(feature = "rolling_window")]
+    pub fn rolling_std(
+        &self,
+        window_size: usize,
+        min_periods: Option<usize>,
+        center: bool,
+    ) -> PyResult<PySeries> {
+        let options = self.rolling_options(window_size, min_periods, center)?;
+        let s = self.0.rolling_std(options).map_err(PyPolarsErr::from)?;
+        Ok(PySeries(s))
+    }
+
+    /// Bin the values into categories, with the bin edges at `breaks`.
+    ///
+    /// Returns a `Categorical` series. If `labels` are given there must be one more label
+    /// than there are breaks, otherwise the labels are the intervals, like `(-inf, 1]`.
+    /// With `left_closed` the intervals include their left edge instead of their right edge.
+    #[cfg(feature = "cutqcut")]
+    pub fn cut(
+        &self,
+        breaks: Vec<f64>,
+        labels: Option<Vec<String>>,
+        left_closed: bool,
+    ) -> PyResult<PySeries> {
+        let labels = cut_labels(labels, breaks.len(), "breaks")?;
+        let s = polars_ops::series::cut(&self.0, breaks, labels, left_closed, false)
+            .map_err(PyPolarsErr::from)?;
+        Ok(PySeries(s))
+    }
+
+    /// Bin the values into categories, with the bin edges at the `quantiles` of the values.
+    ///
+    /// Returns a `Categorical` series. If `labels` are given there must be one more label
+    /// than there are quantiles. Duplicate bin edges raise an error.
+    #[cfg(feature = "cutqcut")]
+    pub fn qcut(&self, quantiles: Vec<f64>, labels: Option<Vec<String>>) -> PyResult<PySeries> {
+        let labels = cut_labels(labels, quantiles.len(), "quantiles")?;
+        let s = polars_ops::series::qcut(&self.0, quantiles, labels, false, false, false)
+            .map_err(PyPolarsErr::from)?;
+        Ok(PySeries(s))
+    }
+
+    /// Compute a histogram of the values.
+    ///
+    /// Returns a frame with the columns `breakpoint`, the right edge of each bin, and
+    /// `count`. Either pass the bin edges as `bins`, or the number of equally wide bins as
+    /// `bin_count`, but not both. Without either, 10 bins are used. The series must be numeric.
+    #[cfg(feature = "hist")]
+    pub fn hist(&self, bins: Option<Vec<f64>>, bin_count: Option<usize>) -> PyResult<PyDataFrame> {
+        if bins.is_some() && bin_count.is_some() {
+            return Err(PyValueError::new_err(
+                "'bins' and 'bin_count' are mutually exclusive, pass only one of them",
+            ));
+        }
+        let dtype = self.0.dtype();
+        if !(dtype.is_integer() || dtype.is_float()) {
+            return Err(PyValueError::new_err(format!(
+                "a histogram requires a numeric series, got series '{}' of dtype {}",
+                self.0.name(),
+                dtype
+            )));
+        }
+        let bins = bins.map(|bins| Series::new("bins".into(), bins));
+        let s = polars_ops::chunked_array::hist_series(&self.0, bin_count, bins, false, true)
+            .map_err(PyPolarsErr::from)?;
+        let df = s.struct_().map_err(PyPolarsErr::from)?.clone().unnest();
+        Ok(PyDataFrame(df))
+    }
+
+    /// Boolean negation, null values stay null.
+    ///
+    /// The series must be of dtype `Boolean`.
+    pub fn not(&self) -> PyResult<PySeries> {
+        let ca = self.0.bool().map_err(PyPolarsErr::from)?;
+        Ok(PySeries((!ca).into_series()))
+    }
+
+    /// Check that the series equals `other`, e.g. in the tests of a plugin.
+    ///
+    /// Raises an `AssertionError` that describes the first difference: the name, length,
+    /// dtype, or the first differing value and its index. Nulls compare equal to nulls.
+    /// Without `check_dtypes` `other` is cast to the dtype of the series first.
+    pub fn assert_series_equal(
+        &self,
+        other: &PySeries,
+        check_dtypes: bool,
+        check_names: bool,
+    ) -> PyResult<()> {
+        let (left, right) = (&self.0, &other.0);
+        if check_names && left.name() != right.name() {
+            return Err(PyAssertionError::new_err(format!(
+                "series have a different name: '{}' != '{}'",
+                left.name(),
+                right.name()
+            )));
+        }
+        if left.len() != right.len() {
+            return Err(PyAssertionError::new_err(format!(
+                "series have a different length: {} != {}",
+                left.len(),
+                right.len()
+            )));
+        }
+        if check_dtypes && left.dtype() != right.dtype() {
+            return Err(PyAssertionError::new_err(format!(
+                "series have a different dtype: {} != {}",
+                left.dtype(),
+                right.dtype()
+            )));
+        }
+        if let Some(index) = first_mismatch(left, right)? {
+            return Err(PyAssertionError::new_err(format!(
+                "series differ at index {index}: {} != {}",
+                left.get(index).map_err(PyPolarsErr::from)?,
+                right.get(index).map_err(PyPolarsErr::from)?
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// The index of the first value where `left` and `right` differ, `right` is cast to the dtype
+/// of `left` if needed. Both must have the same length.
+fn first_mismatch(left: &Series, right: &Series) -> PyResult<Option<usize>> {
+    let right = if left.dtype() == right.dtype() {
+        Cow::Borrowed(right)
+    } else {
+        let right = right.strict_cast(left.dtype()).map_err(|e| {
+            e.wrap_msg(|msg| {
+                format!(
+                    "cannot compare '{}' as {}: {}",
+                    right.name(),
+                    left.dtype(),
+                    msg
+                )
+            })
+        });
+        Cow::Owned(right.map_err(PyPolarsErr::from)?)
+    };
+    let equal = left.equal_missing(&*right).map_err(PyPolarsErr::from)?;
+    Ok(equal.iter().position(|equal| equal != Some(true)))
+}
+
+#[cfg(feature = "cutqcut")]
+fn cut_labels(
+    labels: Option<Vec<String>>,
+    n_edges: usize,
+    edges: &str,
+) -> PyResult<Option<Vec<PlSmallStr>>> {
+    let Some(labels) = labels else {
+        return Ok(None);
+    };
+    if labels.len() != n_edges + 1 {
+        return Err(PyValueError::new_err(format!(
+            "expected {} labels for {} {}, got {}",
+            n_edges + 1,
+            n_edges,
+            edges,
+            labels.len()
+        )));
+    }
+    Ok(Some(labels.into_iter().map(PlSmallStr::from).collect()))
+}
+
+impl PyDataFrame {
+    /// Unwrap the [`DataFrame`], like `DataFrame::from(pydf)`.
+    pub fn into_inner(self) -> DataFrame {
+        self.0
+    }
+
+    /// Count the null values per column.
+    ///
+    /// Returns a single row frame with the same column names as `self` and
+    /// the null counts as values.
+    pub fn null_count(&self) -> PyDataFrame {
+        PyDataFrame(self.0.null_count())
+    }
+
+    /// Read an arrow IPC file, optionally only the given `columns`.
+    ///
+    /// The GIL is released while reading. Failing to open or read the file raises an `OSError`.
+    #[cfg(feature = "ipc")]
+    pub fn read_ipc(
+        py: Python<'_>,
+        path: &str,
+        columns: Option<Vec<String>>,
+    ) -> PyResult<PyDataFrame> {
+        let df = py
+            .allow_threads(|| {
+                let file = std::fs::File::open(path)?;
+                IpcReader::new(file).with_columns(columns).finish()
+            })
+            .map_err(PyPolarsErr::from)?;
+        Ok(PyDataFrame(df))
+    }
+
+    /// Read a parquet file, optionally only the given `columns`.
+    ///
+    /// The GIL is released while reading. Failing to open or read the file raises an `OSError`.
+    #[cfg(feature = "parquet")]
+    pub fn read_parquet(
+        py: Python<'_>,
+        path: &str,
+        columns: Option<Vec<String>>,
+    ) -> PyResult<PyDataFrame> {
+        let df = py
+            .allow_threads(|| {
+                let file = std::fs::File::open(path)?;
+                ParquetReader::new(file).with_columns(columns).finish()
+            })
+            .map_err(PyPolarsErr::from)?;
+        Ok(PyDataFrame(df))
+    }
+
+    /// Drop the rows that contain a null value.
+    ///
+    /// If `subset` is given, only nulls in those columns are considered. Unknown columns
+    /// raise a `ColumnNotFoundError`.
+    pub fn drop_nulls(&self, subset: Option<Vec<String>>) -> PyResult<PyDataFrame> {
+        let df = self
+            .0
+            .drop_nulls(subset.as_deref())
+            .map_err(PyPolarsErr::from)?;
+        Ok(PyDataFrame(df))
+    }
+
+    /// Stack `frames` vertically into a single frame, e.g. to combine a python list of frames.
+    ///
+    /// All frames must have the same column names in the same order, otherwise a
+    /// `SchemaError` is raised. Columns with different but compatible dtypes are cast to their
+    /// supertype, e.g. `Int32` and `Float64` become `Float64`; incompatible dtypes raise a
+    /// `SchemaError` as well. An empty list results in an empty frame and a single frame is
+    /// returned as is. The chunks of the frames are kept, rechunk the result if needed.
+    pub fn concat(frames: Vec<PyDataFrame>) -> PyResult<PyDataFrame> {
+        let mut frames = frames.into_iter().map(|df| df.0);
+        let Some(first) = frames.next() else {
+            return Ok(PyDataFrame(DataFrame::empty()));
+        };
+        let rest = frames.collect::<Vec<_>>();
+
+        let concat = || {
+            let mut dtypes = first.dtypes();
+            for df in &rest {
+                polars_ensure!(
+                    df.get_column_names() == first.get_column_names(),
+                    SchemaMismatch: "cannot concatenate frames with columns {:?} and {:?}",
+                    first.get_column_names(),
+                    df.get_column_names()
+                );
+                for (dtype, c) in dtypes.iter_mut().zip(df.get_columns()) {
+                    *dtype = try_get_supertype(dtype, c.dtype())?;
+                }
+            }
+            let to_supertypes = |df: DataFrame| {
+                if df.dtypes() == dtypes {
+                    return Ok(df);
+                }
+                let columns = df
+                    .take_columns()
+                    .into_iter()
+                    .zip(&dtypes)
+                    .map(|(c, dtype)| c.cast(dtype))
+                    .collect::<PolarsResult<Vec<_>>>()?;
+                DataFrame::new(columns)
+            };
+            let mut out = to_supertypes(first.clone())?;
+            for df in rest {
+                out.vstack_mut(&to_supertypes(df)?)?;
+            }
+            Ok(out)
+        };
+        Ok(PyDataFrame(concat().map_err(PyPolarsErr::from)?))
+    }
+
+    /// Replace the `NaN` values in the float columns with `value`, or with null if `value`
+    /// is `None`. Other columns are left as is.
+    pub fn fill_nan(&self, value: Option<f64>) -> PyResult<PyDataFrame> {
+        let columns = self
+            .0
+            .get_columns()
+            .iter()
+            .map(|c| match c.dtype() {
+                DataType::Float32 => {
+                    let ca = c.f32().unwrap().apply(|v| match v {
+                        Some(v) if v.is_nan() => value.map(|fill| fill as f32),
+                        v => v,
+                    });
+                    ca.into_column()
+                }
+                DataType::Float64 => {
+                    let ca = c.f64().unwrap().apply(|v| match v {
+                        Some(v) if v.is_nan() => value,
+                        v => v,
+                    });
+                    ca.into_column()
+                }
+                _ => c.clone(),
+            })
+            .collect::<Vec<_>>();
+        let df = DataFrame::new(columns).map_err(PyPolarsErr::from)?;
+        Ok(PyDataFrame(df))
+    }
+
+    /// Cast the columns of the frame to the dtypes in `schema`.
+    ///
+    /// With `strict` a value that can't be cast raises an error that names the column,
+    /// otherwise it becomes null. Every column in `schema` must exist in the frame, columns
+    /// that aren't in `schema` are left as is. The column order of the frame is kept.
+    pub fn cast_to_schema(&self, schema: PySchema, strict: bool) -> PyResult<PyDataFrame> {
+        for name in schema.0.iter_names() {
+            if self.0.get_column_index(name).is_none() {
+                return Err(PyPolarsErr::from(
+                    polars_err!(ColumnNotFound: "column '{}' of the schema is not in the frame", name),
+                )
+                .into());
+            }
+        }
+        let columns = self
+            .0
+            .get_columns()
+            .iter()
+            .map(|c| match schema.0.get(c.name()) {
+                Some(dtype) if dtype != c.dtype() => {
+                    let out = if strict {
+                        c.strict_cast(dtype)
+                    } else {
+                        c.cast(dtype)
+                    };
+                    out.map_err(|e| {
+                        e.wrap_msg(|msg| {
+                            format!("cannot cast column '{}' to {}: {}", c.name(), dtype, msg)
+                        })
+                    })
+                }
+                _ => Ok(c.clone()),
+            })
+            .collect::<PolarsResult<Vec<_>>>()
+            .map_err(PyPolarsErr::from)?;
+        let df = DataFrame::new(columns).map_err(PyPolarsErr::from)?;
+        Ok(PyDataFrame(df))
+    }
+
+    /// Check that the frame equals `other`, e.g. in the tests of a plugin.
+    ///
+    /// Raises an `AssertionError` that describes the first difference: the shape, a column
+    /// name, a dtype, or the row and column of the first differing value. Nulls compare
+    /// equal to nulls. Without `check_names` the columns are compared by position, without
+    /// `check_dtypes` the columns of `other` are cast to the dtypes of the frame first.
+    pub fn assert_frame_equal(
+        &self,
+        other: &PyDataFrame,
+        check_dtypes: bool,
+        check_names: bool,
+    ) -> PyResult<()> {
+        let (left, right) = (&self.0, &other.0);
+        if left.shape() != right.shape() {
+            return Err(PyAssertionError::new_err(format!(
+                "frames have a different shape: {:?} != {:?}",
+                left.shape(),
+                right.shape()
+            )));
+        }
+        for (l, r) in left.get_columns().iter().zip(right.get_columns()) {
+            let (l, r) = (l.as_materialized_series(), r.as_materialized_series());
+            if check_names && l.name() != r.name() {
+                return Err(PyAssertionError::new_err(format!(
+                    "frames have different column names: {:?} != {:?}",
+                    left.get_column_names(),
+                    right.get_column_names()
+                )));
+            }
+            if check_dtypes && l.dtype() != r.dtype() {
+                return Err(PyAssertionError::new_err(format!(
+                    "column '{}' has a different dtype: {} != {}",
+                    l.name(),
+                    l.dtype(),
+                    r.dtype()
+                )));
+            }
+            if let Some(row) = first_mismatch(l, r)? {
+                return Err(PyAssertionError::new_err(format!(
+                    "frames differ at row {row}, column '{}': {} != {}",
+                    l.name(),
+                    l.get(row).map_err(PyPolarsErr::from)?,
+                    r.get(row).map_err(PyPolarsErr::from)?
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Get the values of the row at `index`.
+    ///
+    /// Raises an `IndexError` if `index` is out of bounds.
+    /// This is slow, so it shouldn't be used to iterate over the rows of a frame.
+    pub fn row(&self, index: usize) -> PyResult<Vec<PyAnyValue<'_>>> {
+        if index >= self.0.height() {
+            return Err(PyIndexError::new_err(format!(
+                "row index {index} is out of bounds for frame with height {}",
+                self.0.height()
+            )));
+        }
+        let row = self.0.get_row(index).map_err(PyPolarsErr::from)?;
+        Ok(row.0.into_iter().map(PyAnyValue).collect())
+    }
+
+    /// Explode the list columns `columns` into rows.
+    ///
+    /// All `columns` must be of a list (or array) dtype. When multiple columns are exploded
+    /// at once, their lists must have the same number of elements in every row.
+    pub fn explode(&self, columns: Vec<String>) -> PyResult<PyDataFrame> {
+        for name in &columns {
+            let dtype = self.0.column(name).map_err(PyPolarsErr::from)?.dtype();
+            #[cfg(feature = "dtype-array")]
+            let is_list = matches!(dtype, DataType::List(_) | DataType::Array(_, _));
+            #[cfg(not(feature = "dtype-array"))]
+            let is_list = matches!(dtype, DataType::List(_));
+            if !is_list {
+                return Err(PyPolarsErr::from(polars_err!(
+                    InvalidOperation: "cannot explode column '{}' of dtype {}, expected a list", name, dtype
+                ))
+                .into());
+            }
+        }
+        let df = self.0.explode(columns).map_err(PyPolarsErr::from)?;
+        Ok(PyDataFrame(df))
+    }
+
+    /// Group by the columns `by` and aggregate.
+    ///
+    /// `aggs` is a list of `(column, aggregation)` pairs, where the aggregation is one of
+    /// `'sum'`, `'mean'`, `'median'`, `'min'`, `'max'`, `'first'`, `'last'`, `'n_unique'`,
+    /// `'count'` (the number of non-null values), `'std'` or `'var'`.
+    /// The aggregated columns are named `"{column}_{aggregation}"`.
+    ///
+    /// The groups are returned in the order of their first occurrence.
+    pub fn group_by_agg(&self, by: &[&str], aggs: &[(&str, &str)]) -> PyResult<PyDataFrame> {
+        let gb = self
+            .0
+            .group_by_stable(by.iter().copied())
+            .map_err(PyPolarsErr::from)?;
+        let groups = gb.get_groups();
+
+        let mut columns = gb.keys();
+        for (name, agg) in aggs {
+            let column = self.0.column(name).map_err(PyPolarsErr::from)?;
+            // SAFETY: the groups are computed from the same frame, so they are in bounds.
+            let mut out = unsafe {
+                match *agg {
+                    "sum" => column.agg_sum(groups),
+                    "mean" => column.agg_mean(groups),
+                    "median" => column.agg_median(groups),
+                    "min" => column.agg_min(groups),
+                    "max" => column.agg_max(groups),
+                    "first" => column.agg_first(groups),
+                    "last" => column.agg_last(groups),
+                    "n_unique" => column.agg_n_unique(groups),
+                    "count" => column.agg_valid_count(groups),
+                    "std" => column.agg_std(groups, 1),
+                    "var" => column.agg_var(groups, 1),
+                    v => {
+                        return Err(PyValueError::new_err(format!(
+                            "aggregation must be one of {{'sum', 'mean', 'median', 'min', 'max', 'first', 'last', 'n_unique', 'count', 'std', 'var'}}, got {v}",
+                        )))
+                    }
+                }
+            };
+            out.rename(format!("{name}_{agg}").into());
+            columns.push(out);
+        }
+        let df = DataFrame::new(columns).map_err(PyPolarsErr::from)?;
+        Ok(PyDataFrame(df))
+    }
+
+    /// Group by the columns `by` and sum the columns `values`.
+    ///
+    /// See [`PyDataFrame::group_by_agg`].
+    pub fn group_by_sum(&self, by: &[&str], values: &[&str]) -> PyResult<PyDataFrame> {
+        let aggs = values.iter().map(|v| (*v, "sum")).collect::<Vec<_>>();
+        self.group_by_agg(by, &aggs)
+    }
+
+    /// Group by the columns `by` and compute the mean of the columns `values`.
+    ///
+    /// See [`PyDataFrame::group_by_agg`].
+    pub fn group_by_mean(&self, by: &[&str], values: &[&str]) -> PyResult<PyDataFrame> {
+        let aggs = values.iter().map(|v| (*v, "mean")).collect::<Vec<_>>();
+        self.group_by_agg(by, &aggs)
+    }
+
+    /// Convert the frame to a numpy structured array, with one field per column.
+    ///
+    /// Only fixed-width dtypes are supported: booleans, integers, floats and the temporal
+    /// dtypes `Date`, `Datetime` and `Duration` (time zones are dropped). Null values are only
+    /// allowed in float columns, where they become `NaN`.
+    #[cfg(feature = "numpy")]
+    pub fn to_numpy_structured(&self, py: Python<'_>) -> PyResult<PyObject> {
+        fn numpy_format(dtype: &DataType) -> Option<String> {
+            let fmt = match dtype {
+                DataType::Boolean => "?",
+                DataType::Int8 => "i1",
+                DataType::Int16 => "i2",
+                DataType::Int32 => "i4",
+                DataType::Int64 => "i8",
+                DataType::UInt8 => "u1",
+                DataType::UInt16 => "u2",
+                DataType::UInt32 => "u4",
+                DataType::UInt64 => "u8",
+                DataType::Float32 => "f4",
+                DataType::Float64 => "f8",
+                DataType::Date => "datetime64[D]",
+                DataType::Datetime(tu, _) => return Some(format!("datetime64[{}]", tu.to_ascii())),
+                DataType::Duration(tu) => return Some(format!("timedelta64[{}]", tu.to_ascii())),
+                _ => return None,
+            };
+            Some(fmt.to_string())
+        }
+
+        let mut fields = Vec::with_capacity(self.0.width());
+        for column in self.0.get_columns() {
+            let dtype = column.dtype();
+            let Some(fmt) = numpy_format(dtype) else {
+                return Err(PyTypeError::new_err(format!(
+                    "column '{}' of dtype {} cannot be converted to a numpy structured array",
+                    column.name(),
+                    dtype
+                )));
+            };
+            if !dtype.is_float() && column.null_count() > 0 {
+                return Err(PyValueError::new_err(format!(
+                    "column '{}' of dtype {} contains nulls, which numpy cannot represent",
+                    column.name(),
+                    dtype
+                )));
+            }
+            fields.push((column.name().as_str(), fmt));
+        }
+
+        let numpy = py.import_bound("numpy")?;
+        let dtype = numpy.call_method1(intern!(py, "dtype"), (fields,))?;
+        let out = numpy.call_method1(intern!(py, "empty"), (self.0.height(), dtype))?;
+        for column in self.0.get_columns() {
+            let values = PySeries(column.as_materialized_series().clone())
+                .try_into_py(py)?
+                .call_method0(py, intern!(py, "to_numpy"))?;
+            out.set_item(column.name().as_str(), values)?;
+        }
+        Ok(out.into_py(py))
+    }
+}
+
+impl PyDataType {
+    /// Whether the dtype is a `List`, `Array` or `Struct`.
+    pub fn is_nested(&self) -> bool {
+        self.0.is_nested()
+    }
+
+    /// The innermost dtype of (nested) `List` and `Array` dtypes, e.g. `Int64` for
+    /// `List(Array(Int64, 2))`. Non-nested dtypes return themselves.
+    ///
+    /// Returns `None` if the innermost dtype is a `Struct`, as a struct has no single leaf dtype.
+    pub fn leaf_dtype(&self) -> Option<PyDataType> {
+        let leaf = self.0.leaf_dtype();
+        (!leaf.is_struct()).then(|| PyDataType(leaf.clone()))
+    }
+
+    /// The polars dtype of an arrow field, e.g. of a schema received from another arrow library.
+    ///
+    /// Arrow extension types (e.g. `arrow.json` or GeoArrow's `geoarrow.wkb`) are mapped to
+    /// the polars dtype of their storage type, also if they are nested. The extension name and
+    /// metadata are dropped, as polars dtypes can't carry them. Objects exported by polars
+    /// map back to `Object` if the `object` feature is active.
+    ///
+    /// Raises a `TypeError` if the field, or its extension storage, has an arrow dtype that
+    /// polars can't represent.
+    pub fn from_arrow_field(field: &ArrowField) -> PyResult<PyDataType> {
+        let mut field = field.clone();
+        field.dtype = arrow_storage_dtype(&field.dtype, &field.name)?;
+        Ok(PyDataType(DataType::from_arrow_field(&field)))
+    }
+}
+
+/// Replace the extension types in `dtype` by their storage types and check that polars can
+/// represent the result.
+fn arrow_storage_dtype(dtype: &ArrowDataType, name: &str) -> PyResult<ArrowDataType> {
+    let map_field = |field: &ArrowField| -> PyResult<ArrowField> {
+        let mut field = field.clone();
+        field.dtype = arrow_storage_dtype(&field.dtype, &field.name)?;
+        Ok(field)
+    };
+    let dtype = match dtype {
+        #[cfg(feature = "object")]
+        ArrowDataType::Extension(ext) if ext.name.as_str() == EXTENSION_NAME => dtype.clone(),
+        ArrowDataType::Extension(ext) => arrow_storage_dtype(&ext.inner, name)
+            .map_err(|e| {
+                PyTypeError::new_err(format!(
+                    "arrow extension type '{}' of field '{name}' has an unsupported storage type: {e}",
+                    ext.name
+                ))
+            })?,
+        ArrowDataType::List(field) => ArrowDataType::List(Box::new(map_field(field)?)),
+        ArrowDataType::LargeList(field) => ArrowDataType::LargeList(Box::new(map_field(field)?)),
+        #[cfg(feature = "dtype-array")]
+        ArrowDataType::FixedSizeList(field, size) => {
+            ArrowDataType::FixedSizeList(Box::new(map_field(field)?), *size)
+        }
+        #[cfg(feature = "dtype-struct")]
+        ArrowDataType::Struct(fields) => {
+            ArrowDataType::Struct(fields.iter().map(map_field).collect::<PyResult<_>>()?)
+        }
+        #[cfg(feature = "dtype-categorical")]
+        ArrowDataType::Dictionary(key, values, sorted) => ArrowDataType::Dictionary(
+            *key,
+            Box::new(arrow_storage_dtype(values, name)?),
+            *sorted,
+        ),
+        #[cfg(feature = "dtype-decimal")]
+        ArrowDataType::Decimal(_, _) => dtype.clone(),
+        #[cfg(feature = "dtype-i128")]
+        ArrowDataType::Int128 => dtype.clone(),
+        ArrowDataType::Null
+        | ArrowDataType::Boolean
+        | ArrowDataType::Int8
+        | ArrowDataType::Int16
+        | ArrowDataType::Int32
+        | ArrowDataType::Int64
+        | ArrowDataType::UInt8
+        | ArrowDataType::UInt16
+        | ArrowDataType::UInt32
+        | ArrowDataType::UInt64
+        | ArrowDataType::Float32
+        | ArrowDataType::Float64
+        | ArrowDataType::Date32
+        | ArrowDataType::Date64
+        | ArrowDataType::Timestamp(_, _)
+        | ArrowDataType::Duration(_)
+        | ArrowDataType::Time32(_)
+        | ArrowDataType::Time64(_)
+        | ArrowDataType::Utf8
+        | ArrowDataType::LargeUtf8
+        | ArrowDataType::Utf8View
+        | ArrowDataType::Binary
+        | ArrowDataType::LargeBinary
+        | ArrowDataType::BinaryView
+        | ArrowDataType::FixedSizeBinary(_) => dtype.clone(),
+        dt => {
+            return Err(PyTypeError::new_err(format!(
+                "arrow dtype {dt:?} of field '{name}' can't be represented in polars, or the plugin isn't compiled with the right features",
+            )))
+        }
+    };
+    Ok(dtype)
+}
+
+/// The format used to (de)serialize the logical plan of a [`PyLazyFrame`].
+///
+/// Both formats serialize the plan types of this polars version as they are, so a plan can only
+/// be deserialized by the same polars version that serialized it, in either format.
+#[cfg(feature = "lazy")]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SerializeFormat {
+    /// CBOR, this is the format python polars uses to pickle a `LazyFrame`.
+    #[default]
+    Binary,
+    /// JSON, this is more verbose, but it can be inspected.
+    Json,
+}
+
+#[cfg(feature = "lazy")]
+impl PyLazyFrame {
+    /// A `LazyFrame` that reads from a rust data source, like [`LazyFrame::anonymous_scan`].
+    ///
+    /// Returned to python, the frame stays a rust plan that python polars collects through an
+    /// io source, see `polars.io.plugins.register_io_source`. The projection, predicate and
+    /// slice of the python query are pushed into the scan as far as `function` allows. Python
+    /// polars can't serialize such a frame, so it can't be extracted as a [`PyLazyFrame`]
+    /// again.
+    pub fn anonymous_scan(
+        function: Arc<dyn AnonymousScan>,
+        args: ScanArgsAnonymous,
+    ) -> PyResult<PyLazyFrame> {
+        let lf = LazyFrame::anonymous_scan(function, args).map_err(PyPolarsErr::from)?;
+        Ok(PyLazyFrame(lf))
+    }
+
+    /// Serialize the logical plan, e.g. to store it and [`PyLazyFrame::deserialize`] it later.
+    ///
+    /// In memory data in the plan is serialized as well.
+    pub fn serialize(&self, format: SerializeFormat) -> PyResult<Vec<u8>> {
+        let mut writer: Vec<u8> = vec![];
+        match format {
+            SerializeFormat::Binary => {
+                ciborium::ser::into_writer(&self.0.logical_plan, &mut writer)
+                    .map_err(|e| PyPolarsErr::Other(format!("{}", e)))?
+            }
+            SerializeFormat::Json => serde_json::to_writer(&mut writer, &self.0.logical_plan)
+                .map_err(|e| PyPolarsErr::Other(format!("{}", e)))?,
+        }
+        Ok(writer)
+    }
+
+    /// Deserialize a logical plan created by [`PyLazyFrame::serialize`], or by `LazyFrame.serialize`
+    /// in python polars.
+    ///
+    /// Any failure raises a `DeserializationError`. A plan that is well-formed but doesn't match
+    /// the plan types of this polars version was most likely written by another polars version,
+    /// in either format the error then names the version this library is built against. Other
+    /// errors, e.g. of truncated input, are reported as is.
+    pub fn deserialize(bytes: &[u8], format: SerializeFormat) -> PyResult<Self> {
+        Self::deserialize_from(bytes, format, None)
+    }
+
+    /// Deserialize a plan that was serialized by `caller`, e.g. `"python polars 1.19.0"`, which
+    /// is reported next to the polars version of this library if the versions don't match.
+    fn deserialize_from(
+        bytes: &[u8],
+        format: SerializeFormat,
+        caller: Option<&str>,
+    ) -> PyResult<Self> {
+        let lp: DslPlan = match format {
+            SerializeFormat::Binary => ciborium::de::from_reader(bytes).map_err(|e| {
+                deserialization_error("LazyFrame", &e, is_cbor_type_mismatch(&e), caller)
+            }),
+            SerializeFormat::Json => serde_json::from_slice(bytes).map_err(|e| {
+                let type_mismatch = e.classify() == serde_json::error::Category::Data;
+                deserialization_error("LazyFrame", &e, type_mismatch, caller)
+            }),
+        }?;
+        Ok(PyLazyFrame(LazyFrame::from(lp)))
+    }
+
+    /// Join with `other` on the given columns, without collecting either frame.
+    ///
+    /// `how` is one of `'inner'`, `'left'`, `'right'`, `'full'` or `'cross'`, and with the
+    /// `semi_anti_join` feature also `'semi'` or `'anti'`. For a `'cross'` join `left_on`
+    /// and `right_on` must be empty.
+    pub fn join(
+        self,
+        other: PyLazyFrame,
+        left_on: &[&str],
+        right_on: &[&str],
+        how: &str,
+    ) -> PyResult<PyLazyFrame> {
+        let how = match how {
+            "inner" => JoinType::Inner,
+            "left" => JoinType::Left,
+            "right" => JoinType::Right,
+            "full" => JoinType::Full,
+            "cross" => JoinType::Cross,
+            #[cfg(feature = "semi_anti_join")]
+            "semi" => JoinType::Semi,
+            #[cfg(feature = "semi_anti_join")]
+            "anti" => JoinType::Anti,
+            v => {
+                return Err(PyValueError::new_err(format!(
+                    "`how` must be one of {{'inner', 'left', 'right', 'full', 'cross', 'semi', 'anti'}}, got {v}, or the plugin isn't compiled with the right features",
+                )))
+            }
+        };
+        if left_on.len() != right_on.len() {
+            return Err(PyValueError::new_err(format!(
+                "`left_on` and `right_on` must have the same length, got {} and {}",
+                left_on.len(),
+                right_on.len()
+            )));
+        }
+        if matches!(how, JoinType::Cross) != left_on.is_empty() {
+            return Err(PyValueError::new_err(
+                "a 'cross' join doesn't take join columns, all other joins need at least one",
+            ));
+        }
+
+        let left_on = left_on.iter().map(|name| col(*name)).collect::<Vec<_>>();
+        let right_on = right_on.iter().map(|name| col(*name)).collect::<Vec<_>>();
+        Ok(PyLazyFrame(self.0.join(
+            other.0,
+            left_on,
+            right_on,
+            JoinArgs::new(how),
+        )))
+    }
+
+    /// Filter the rows with `predicate`, without collecting the frame.
+    ///
+    /// The predicate becomes part of the plan, so the optimizer can still push it down
+    /// when the frame is collected.
+    pub fn filter(self, predicate: PyExpr) -> PyLazyFrame {
+        PyLazyFrame(self.0.filter(predicate.0))
+    }
+
+    /// Select `exprs`, without collecting the frame.
+    pub fn select(self, exprs: Vec<PyExpr>) -> PyLazyFrame {
+        let exprs = exprs.into_iter().map(|e| e.0).collect::<Vec<_>>();
+        PyLazyFrame(self.0.select(exprs))
+    }
+
+    /// Collect the frame and profile the execution of the plan.
+    ///
+    /// Returns the result and a frame with the `node`, `start` and `end` of every executed
+    /// node of the plan, in microseconds. The GIL is released while the plan runs.
+    pub fn profile(self, py: Python<'_>) -> PyResult<(PyDataFrame, PyDataFrame)> {
+        let (df, timings) = py
+            .allow_threads(|| self.0.profile())
+            .map_err(PyPolarsErr::from)?;
+        Ok((PyDataFrame(df), PyDataFrame(timings)))
+    }
+
+    /// Vertically concatenate `frames`, without collecting them.
+    pub fn concat(frames: Vec<PyLazyFrame>, rechunk: bool) -> PyResult<PyLazyFrame> {
+        let frames = frames.into_iter().map(|lf| lf.0).collect::<Vec<_>>();
+        let args = UnionArgs {
+            rechunk,
+            ..Default::default()
+        };
+        let lf = polars_lazy::dsl::concat(frames, args).map_err(PyPolarsErr::from)?;
+        Ok(PyLazyFrame(lf))
+    }
+
+    /// Create a lazy frame that scans an in-memory frame, imported through the arrow C stream
+    /// interface instead of the serialized plan.
+    ///
+    /// `ob` can be a polars `DataFrame` or any other object that implements
+    /// `__arrow_c_stream__`, like a pyarrow `Table`. Unlike extracting a `LazyFrame`, the data
+    /// isn't copied, which matters for large frames.
+    pub fn from_arrow_backed(ob: &Bound<'_, PyAny>) -> PyResult<PyLazyFrame> {
+        let df = PyDataFrame::from_arrow_c_stream(ob)?;
+        Ok(PyLazyFrame(df.0.lazy()))
+    }
+}
+
+/// The arrow field of the exported chunk `array` of `s`.
+///
+/// It carries the metadata polars uses for logical types like `Enum`, so that they survive
+/// the conversion. Extension types are part of the arrow dtype of the chunk.
+pub(crate) fn export_field(s: &Series, array: &ArrayRef, compat_level: CompatLevel) -> ArrowField {
+    let mut field = s.field().to_arrow(compat_level);
+    field.dtype = array.dtype().clone();
+    field
+}
+
+/// The sorted flag of a python polars `Series`, which is lost in the arrow conversion.
+fn sorted_flag(ob: &Bound<'_, PyAny>) -> IsSorted {
+    match ob.getattr(intern!(ob.py(), "flags")) {
+        Ok(flags) => sorted_flag_from_flags(&flags),
+        Err(_) => IsSorted::Not,
+    }
+}
+
+/// Set the sorted flags of the columns of `df`, that are lost in the arrow conversion, from
+/// `DataFrame.flags` of the python polars frame `ob`, a dict of the flags of every column.
+fn set_sorted_flags(ob: &Bound<'_, PyAny>, df: &mut DataFrame) {
+    let Ok(flags) = ob.getattr(intern!(ob.py(), "flags")) else {
+        return;
+    };
+    // SAFETY: only the sorted flags change, not the length, names or dtypes of the columns.
+    for c in unsafe { df.get_columns_mut() } {
+        if let Ok(column_flags) = flags.get_item(c.name().as_str()) {
+            c.set_sorted_flag(sorted_flag_from_flags(&column_flags));
+        }
+    }
+}
+
+/// The sorted flag in the `flags` dict of a python polars `Series`.
+fn sorted_flag_from_flags(flags: &Bound<'_, PyAny>) -> IsSorted {
+    let is_set = |flag: &str| {
+        flags
+            .get_item(flag)
+            .and_then(|value| value.extract::<bool>())
+            .unwrap_or(false)
+    };
+    if is_set("SORTED_ASC") {
+        IsSorted::Ascending
+    } else if is_set("SORTED_DESC") {
+        IsSorted::Descending
+    } else {
+        IsSorted::Not
+    }
+}
+
+/// Whether `ob` is arrow data that isn't a python polars `Series`, e.g. a pyarrow `Array`.
+fn is_arrow_array(ob: &Bound<'_, PyAny>) -> PyResult<bool> {
+    let py = ob.py();
+    if ob.is_instance(SERIES.bind(py))? {
+        return Ok(false);
+    }
+    if ob.hasattr(intern!(py, "__arrow_c_array__"))?
+        || ob.hasattr(intern!(py, "__arrow_c_stream__"))?
+    {
+        return Ok(true);
+    }
+    // Older pyarrow versions don't implement the PyCapsule interface.
+    match pyarrow(py) {
+        Ok(pa) => ob.is_instance(&pa.getattr(intern!(py, "Array"))?),
+        Err(_) => Ok(false),
+    }
+}
+
+impl PySeries {
+    /// Import an arrow array that isn't a python polars `Series` as a series named `name`.
+    ///
+    /// Accepts objects that implement `__arrow_c_array__` or `__arrow_c_stream__`, like a
+    /// pyarrow `Array` or `ChunkedArray`, and pyarrow arrays of versions that predate the
+    /// PyCapsule interface. A stream becomes a series with a chunk per array.
+    ///
+    /// The [`FromPyObject`] implementation of [`PySeries`] uses this path with an empty
+    /// name for such objects.
+    pub fn from_arrow(ob: &Bound<'_, PyAny>, name: &str) -> PyResult<PySeries> {
+        let py = ob.py();
+        let (mut field, chunks) = if ob.hasattr(intern!(py, "__arrow_c_array__"))? {
+            let (schema, array) = ob
+                .call_method0(intern!(py, "__arrow_c_array__"))?
+                .extract::<(Bound<'_, PyCapsule>, Bound<'_, PyCapsule>)>()?;
+            let (field, array) = ffi::to_rust::import_array_pycapsules(&schema, &array)?;
+            (field, vec![array])
+        } else if ob.hasattr(intern!(py, "__arrow_c_stream__"))? {
+            let capsule = ffi::to_rust::call_arrow_c_stream(ob)?;
+            ffi::to_rust::import_stream_as_chunks(&capsule)?
+        } else {
+            let (field, array) = ffi::to_rust::field_and_array_to_rust(ob)?;
+            (field, vec![array])
+        };
+        field.name = PlSmallStr::from(name);
+        let s = ffi::to_rust::series_from_chunks(&field, chunks).map_err(PyPolarsErr::from)?;
+        ffi_trace!(
+            "import series '{}' from arrow: n_chunks={}, dtype={}",
+            s.name(),
+            s.n_chunks(),
+            s.dtype()
+        );
+        Ok(PySeries(s))
+    }
+}
+
+impl<'a> FromPyObject<'a> for PySeries {
+    fn extract_bound(ob: &Bound<'a, PyAny>) -> PyResult<Self> {
+        if is_arrow_array(ob)? {
+            return PySeries::from_arrow(ob, "");
+        }
+        let sorted = sorted_flag(ob);
+        let ob = ob.call_method0("rechunk")?;
+
+        let name = ob.getattr("name")?;
+        let py_name = name.str()?;
+        let name = py_name.to_cow()?;
+
+        let kwargs = PyDict::new_bound(ob.py());
+        if let Ok(compat_level) = ob.call_method0("_newest_compat_level") {
+            let compat_level = crate::compat_level(compat_level.extract().unwrap());
+            kwargs.set_item("compat_level", compat_level.get_level())?;
+        }
+        let arr = ob.call_method("to_arrow", (), Some(&kwargs))?;
+        // Keep the metadata of the field, e.g. of an extension type.
+        let (mut field, arr) = ffi::to_rust::field_and_array_to_rust(&arr)?;
+        field.name = PlSmallStr::from(name.as_ref());
+        let mut s = Series::try_from((&field, vec![arr])).map_err(PyPolarsErr::from)?;
+        s.set_sorted_flag(sorted);
+        ffi_trace!(
+            "import series '{}' via to_arrow: compat_level={:?}, dtype={}",
+            s.name(),
+            kwargs
+                .get_item("compat_level")?
+                .map(|level| level.to_string()),
+            s.dtype()
+        );
+        Ok(PySeries(s))
+    }
+}
+
+impl PyDataFrame {
+    /// Import any python object that implements `__arrow_c_stream__`, like a pyarrow `Table`
+    /// or `RecordBatchReader`, without going through python polars.
+    ///
+    /// The fields of the streamed record batches become the columns, struct fields stay
+    /// struct columns. An empty stream results in a frame without rows but with the schema
+    /// of the stream.
+    ///
+    /// The [`FromPyObject`] implementation of [`PyDataFrame`] also uses this path if the
+    /// object supports it.
+    pub fn from_arrow_c_stream(ob: &Bound<'_, PyAny>) -> PyResult<PyDataFrame> {
+        let capsule = ffi::to_rust::call_arrow_c_stream(ob)?;
+        PyDataFrame::from_arrow_capsule(&capsule, true)
+    }
+
+    /// Import a pyarrow `Table` or `RecordBatchReader`, or any other object that exposes its
+    /// record batches, without going through python polars.
+    ///
+    /// Objects that implement `__arrow_c_stream__` are imported like
+    /// [`PyDataFrame::from_arrow_c_stream`]. Otherwise the batches returned by `to_batches()`
+    /// are imported array by array, with a chunk per batch. The column names come from the
+    /// arrow schema. Dictionary encoded string columns become `Categorical` columns, which
+    /// requires the `dtype-categorical` feature.
+    pub fn from_arrow_record_batches(ob: &Bound<'_, PyAny>) -> PyResult<PyDataFrame> {
+        if let Ok(capsule) = ffi::to_rust::call_arrow_c_stream(ob) {
+            return PyDataFrame::from_arrow_capsule(&capsule, true);
+        }
+        let df = ffi::to_rust::import_record_batches(ob)?;
+        ffi_trace!(
+            "import frame via to_batches: width={}, n_chunks={}",
+            df.width(),
+            df.first_col_n_chunks()
+        );
+        Ok(PyDataFrame(df))
+    }
+
+    fn from_arrow_capsule(capsule: &Bound<'_, PyCapsule>, checked: bool) -> PyResult<PyDataFrame> {
+        let df = ffi::to_rust::import_stream_as_dataframe(capsule, checked)?;
+        ffi_trace!(
+            "import frame via __arrow_c_stream__: width={}, n_chunks={}",
+            df.width(),
+            df.first_col_n_chunks()
+        );
+        Ok(PyDataFrame(df))
+    }
+
+    /// Export the frame as an arrow C stream, following the
+    /// [Arrow PyCapsule Interface](https://arrow.apache.org/docs/format/CDataInterface/PyCapsuleInterface.html).
+    ///
+    /// Every chunk of the frame is handed out as one record batch without copying the
+    /// buffers. If `requested_schema` is given, its columns are selected in its order and
+    /// cast to its dtypes; a column that is missing or can't be cast raises an error. Without
+    /// a requested schema, strings are exported as arrow view types.
+    ///
+    /// Expose this on a `#[pyclass]` to let any arrow consumer, e.g. `pyarrow.table`,
+    /// read the frame directly.
+    pub fn __arrow_c_stream__<'py>(
+        &self,
+        py: Python<'py>,
+        requested_schema: Option<Bound<'py, PyAny>>,
+    ) -> PyResult<Bound<'py, PyCapsule>> {
+        let stream = ffi::to_py::dataframe_to_stream(self.0.clone(), requested_schema.as_ref())?;
+        ffi_trace!(
+            "export frame via __arrow_c_stream__: width={}",
+            self.0.width()
+        );
+        let name = CString::new("arrow_array_stream").unwrap();
+        PyCapsule::new_bound(py, stream, Some(name))
+    }
+}
+
+impl PyDataFrame {
+    /// Extract a python polars `DataFrame` without checking that its columns have the same
+    /// length and unique names.
+    ///
+    /// [`FromPyObject`] validates the columns, because a duck-typed object could return
+    /// anything from `get_columns`. This skips that validation.
+    ///
+    /// # Safety
+    /// `ob` must be a genuine python polars `DataFrame`, or otherwise return columns of equal
+    /// length with unique names.
+    pub unsafe fn extract_unchecked(ob: &Bound<'_, PyAny>) -> PyResult<PyDataFrame> {
+        extract_frame(ob, false)
+    }
+}
+
+fn extract_frame(ob: &Bound<'_, PyAny>, checked: bool) -> PyResult<PyDataFrame> {
+    // Import the whole frame as a single arrow stream. Unlike the per series path below,
+    // this crosses the FFI boundary once instead of calling `rechunk` and `to_arrow` for
+    // every column, which dominates the conversion of wide frames.
+    if let Ok(capsule) = ffi::to_rust::call_arrow_c_stream(ob) {
+        let mut df = PyDataFrame::from_arrow_capsule(&capsule, checked)?;
+        set_sorted_flags(ob, &mut df.0);
+        return Ok(df);
+    }
+
+    // Fall back to converting the frame series by series, e.g. if the installed polars
+    // version doesn't implement `__arrow_c_stream__` or the frame has `Object` columns.
+    let series = ob.call_method0("get_columns")?;
+    let n = ob.getattr("width")?.extract::<usize>()?;
+    let mut columns = Vec::with_capacity(n);
+    for pyseries in series.iter()? {
+        let pyseries = pyseries?;
+        let s = pyseries.extract::<PySeries>()?.0;
+        columns.push(s.into_column());
+    }
+    let df = if checked {
+        DataFrame::new(columns).map_err(PyPolarsErr::from)?
+    } else {
+        // SAFETY: the caller of `extract_unchecked` guarantees that the columns are valid.
+        unsafe { DataFrame::new_no_checks_height_from_first(columns) }
+    };
+    Ok(PyDataFrame(df))
+}
+
+impl<'a> FromPyObject<'a> for PyDataFrame {
+    fn extract_bound(ob: &Bound<'a, PyAny>) -> PyResult<Self> {
+        extract_frame(ob, true)
+    }
+}
+
+/// The columns of a python polars `DataFrame`, converted one at a time.
+///
+/// Unlike [`PyDataFrame`] this doesn't convert any column upfront. Each call to
+/// [`Iterator::next`] fetches the next column with `DataFrame.to_series(index)` and converts it
+/// to a [`PySeries`], so columns after an early `break` are never converted.
+#[derive(Clone)]
+pub struct PyDataFrameColumns<'py> {
+    df: Bound<'py, PyAny>,
+    index: usize,
+    width: usize,
+}
+
+impl<'py> FromPyObject<'py> for PyDataFrameColumns<'py> {
+    fn extract_bound(ob: &Bound<'py, PyAny>) -> PyResult<Self> {
+        let width = ob.getattr(intern!(ob.py(), "width"))?.extract::<usize>()?;
+        Ok(PyDataFrameColumns {
+            df: ob.clone(),
+            index: 0,
+            width,
+        })
+    }
+}
+
+impl Iterator for PyDataFrameColumns<'_> {
+    type Item = PyResult<PySeries>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index >= self.width {
+            return None;
+        }
+        let out = self
+            .df
+            .call_method1(intern!(self.df.py(), "to_series"), (self.index,))
+            .and_then(|s| s.extract::<PySeries>());
+        self.index += 1;
+        Some(out)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let n = self.width - self.index;
+        (n, Some(n))
+    }
+}
+
+impl ExactSizeIterator for PyDataFrameColumns<'_> {}
+
+/// A list of [`Series`] that can be converted to and from python with `pyo3`.
+///
+/// Any python iterable of polars `Series` can be extracted, the order is preserved. Converting
+/// to python gives a `list`.
+#[derive(Debug, Clone, Default)]
+pub struct PySeriesList(pub Vec<Series>);
+
+impl<'py> FromPyObject<'py> for PySeriesList {
+    fn extract_bound(ob: &Bound<'py, PyAny>) -> PyResult<Self> {
+        let mut out = Vec::with_capacity(ob.len().unwrap_or(0));
+        for (i, item) in ob.iter()?.enumerate() {
+            let s = item?.extract::<PySeries>().map_err(|err| {
+                let py = ob.py();
+                let new_err = PyTypeError::new_err(format!(
+                    "could not convert item {i} to a polars Series: {}",
+                    err.value_bound(py)
+                ));
+                new_err.set_cause(py, Some(err));
+                new_err
+            })?;
+            out.push(s.0);
+        }
+        Ok(PySeriesList(out))
+    }
+}
+
+impl IntoPy<PyObject> for PySeriesList {
+    fn into_py(self, py: Python<'_>) -> PyObject {
+        PyList::new_bound(py, self.0.into_iter().map(|s| PySeries(s).into_py(py))).into_py(py)
+    }
+}
 
-        let kwargs = PyDict::new_bound(ob.py());
-        if let Ok(compat_level) = ob.call_method0("_newest_compat_level") {
-            let compat_level = compat_level.extract().unwrap();
-            let compat_level =
-                CompatLevel::with_level(compat_level).unwrap_or(CompatLevel::newest());
-            kwargs.set_item("compat_level", compat_level.get_level())?;
-        }
-        let arr = ob.call_method("to_arrow", (), Some(&kwargs))?;
-        let arr = ffi::to_rust::array_to_rust(&arr)?;
-        let name = name.as_ref();
-        Ok(PySeries(
-            Series::try_from((PlSmallStr::from(name), arr)).map_err(PyPolarsErr::from)?,
-        ))
+impl From<PySeriesList> for Vec<Series> {
+    fn from(value: PySeriesList) -> Self {
+        value.0
     }
 }
 
-impl<'a> FromPyObject<'a> for PyDataFrame {
-    fn extract_bound(ob: &Bound<'a, PyAny>) -> PyResult<Self> {
-        let series = ob.call_method0("get_columns")?;
-        let n = ob.getattr("width")?.extract::<usize>()?;
-        let mut columns = Vec::with_capacity(n);
-        for pyseries in series.iter()? {
-            let pyseries = pyseries?;
-            let s = pyseries.extract::<PySeries>()?.0;
-            columns.push(s.into_column());
-        }
-        unsafe {
-            Ok(PyDataFrame(DataFrame::new_no_checks_height_from_first(
-                columns,
-            )))
-        }
+impl Deref for PySeriesList {
+    type Target = Vec<Series>;
+
+    fn deref(&self) -> &Vec<Series> {
+        &self.0
     }
 }
 
@@ -205,143 +1687,465 @@ impl<'a> FromPyObject<'a> for PyDataFrame {
 impl<'a> FromPyObject<'a> for PyLazyFrame {
     fn extract_bound(ob: &Bound<'a, PyAny>) -> PyResult<Self> {
         let s = ob.call_method0("__getstate__")?.extract::<Vec<u8>>()?;
-        let lp: DslPlan = ciborium::de::from_reader(&*s).map_err(
-            |e| PyPolarsErr::Other(
-                format!("Error when deserializing LazyFrame. This may be due to mismatched polars versions. {}", e)
-            )
-        )?;
-        Ok(PyLazyFrame(LazyFrame::from(lp)))
+        let caller = python_polars_version(ob.py());
+        PyLazyFrame::deserialize_from(&s, SerializeFormat::Binary, caller.as_deref())
     }
 }
 
-#[cfg(feature = "lazy")]
+#[cfg(feature = "expr")]
 impl<'a> FromPyObject<'a> for PyExpr {
     fn extract_bound(ob: &Bound<'a, PyAny>) -> PyResult<Self> {
         let s = ob.call_method0("__getstate__")?.extract::<Vec<u8>>()?;
-        let e: Expr = ciborium::de::from_reader(&*s).map_err(
-            |e| PyPolarsErr::Other(
-                format!("Error when deserializing 'Expr'. This may be due to mismatched polars versions. {}", e)
-            )
-        )?;
+        let e: Expr = ciborium::de::from_reader(&*s).map_err(|e| {
+            let caller = python_polars_version(ob.py());
+            deserialization_error("Expr", &e, is_cbor_type_mismatch(&e), caller.as_deref())
+        })?;
         Ok(PyExpr(e))
     }
 }
 
-impl IntoPy<PyObject> for PySeries {
-    fn into_py(self, py: Python<'_>) -> PyObject {
+/// The version of the installed python polars, e.g. `"python polars 1.19.0"`.
+fn python_polars_version(py: Python<'_>) -> Option<String> {
+    let version = POLARS.bind(py).getattr(intern!(py, "__version__")).ok()?;
+    let version = version.extract::<String>().ok()?;
+    Some(format!("python polars {version}"))
+}
+
+/// Whether the CBOR is well-formed but doesn't match the types it is deserialized to.
+#[cfg(feature = "expr")]
+fn is_cbor_type_mismatch<T>(err: &ciborium::de::Error<T>) -> bool {
+    matches!(err, ciborium::de::Error::Semantic(..))
+}
+
+/// The error for a plan or expression that couldn't be deserialized.
+///
+/// Input that is well-formed but doesn't match the types of this polars version was most
+/// likely serialized by another polars version, so the versions are reported. Other errors,
+/// e.g. of truncated input, are reported as is.
+#[cfg(feature = "expr")]
+fn deserialization_error(
+    what: &str,
+    err: &dyn std::fmt::Display,
+    type_mismatch: bool,
+    caller: Option<&str>,
+) -> PyPolarsErr {
+    if !type_mismatch {
+        return PyPolarsErr::Deserialization(format!("could not deserialize the {what}: {err}"));
+    }
+    let caller = match caller {
+        Some(caller) => format!(", but it was serialized by {caller}"),
+        None => String::new(),
+    };
+    PyPolarsErr::Deserialization(format!(
+        "could not deserialize the {what}: this library is built against rust polars {}{caller}. \
+        The serialized format is only compatible between the same polars versions, build this \
+        library against the polars version of the caller. Error: {err}",
+        polars::VERSION
+    ))
+}
+
+impl PySeries {
+    /// Convert to a python polars `Series`.
+    ///
+    /// Unlike [`IntoPy::into_py`] this raises a python exception instead of panicking, e.g. if
+    /// the installed polars version can't import the data and pyarrow isn't installed.
+    pub fn try_into_py(self, py: Python<'_>) -> PyResult<PyObject> {
+        self.export(py, None)
+    }
+
+    /// Convert to a python polars `Series`, exporting the data with `compat_level` instead of
+    /// the newest level the installed polars version supports.
+    ///
+    /// This is useful if the data is passed on to consumers that don't understand the arrow view
+    /// types, e.g. older pyarrow versions. A level newer than the installed polars version
+    /// supports is lowered to that version's newest level.
+    pub fn try_into_py_with_compat_level(
+        self,
+        py: Python<'_>,
+        compat_level: CompatLevel,
+    ) -> PyResult<PyObject> {
+        self.export(py, Some(compat_level))
+    }
+
+    /// Convert to a python polars `Series` that consists of a single chunk.
+    ///
+    /// The chunks are concatenated before the export, which is cheaper than exporting many
+    /// small chunks and what most consumers want anyway.
+    pub fn rechunked_into_py(mut self, py: Python<'_>) -> PyResult<PyObject> {
+        if self.0.n_chunks() > 1 {
+            self.0 = self.0.rechunk();
+        }
+        self.try_into_py(py)
+    }
+
+    /// Export the series as a single arrow array, following the
+    /// [Arrow PyCapsule Interface](https://arrow.apache.org/docs/format/CDataInterface/PyCapsuleInterface.html).
+    ///
+    /// The chunks of the series are concatenated first. If `requested_schema` is given, the
+    /// series is cast to its dtype; a failing cast raises an error. Without a requested
+    /// schema, strings are exported as arrow view types.
+    ///
+    /// Expose this on a `#[pyclass]` to let any arrow consumer, e.g. `pyarrow.array` or
+    /// nanoarrow, read the series directly, without pyarrow being involved on this side.
+    pub fn __arrow_c_array__<'py>(
+        &self,
+        py: Python<'py>,
+        requested_schema: Option<Bound<'py, PyAny>>,
+    ) -> PyResult<(Bound<'py, PyCapsule>, Bound<'py, PyCapsule>)> {
+        let (schema, array) =
+            ffi::to_py::series_to_c_array(self.0.clone(), requested_schema.as_ref())?;
+        ffi_trace!(
+            "export series '{}' via __arrow_c_array__: dtype={}",
+            self.0.name(),
+            self.0.dtype()
+        );
+        let schema_name = CString::new("arrow_schema").unwrap();
+        let schema = PyCapsule::new_bound(py, schema, Some(schema_name))?;
+        let array_name = CString::new("arrow_array").unwrap();
+        let array = PyCapsule::new_bound(py, array, Some(array_name))?;
+        Ok((schema, array))
+    }
+
+    fn export(mut self, py: Python<'_>, compat_level: Option<CompatLevel>) -> PyResult<PyObject> {
+        // Without any chunks python can't recover the dtype, so export a single empty chunk.
+        if self.0.n_chunks() == 0 {
+            self.0 = Series::new_empty(self.0.name().clone(), self.0.dtype());
+        }
+        // Every chunk is exported on its own, which dominates the conversion of series with
+        // many tiny chunks.
+        if self.0.n_chunks() > MAX_EXPORTED_CHUNKS {
+            self.0 = self.0.rechunk();
+        }
+        // Python polars doesn't know the large binary representation, but it's logically the same as
+        // `Binary`.
+        if self.0.dtype() == &DataType::BinaryOffset {
+            self.0 = self.0.cast(&DataType::Binary).map_err(PyPolarsErr::from)?;
+        }
+        #[cfg(feature = "object")]
+        if self.0.dtype().is_object() && !is_object_builder_registered() {
+            return Err(PyTypeError::new_err(format!(
+                "cannot convert object series '{}' to python: no object type is registered in \
+                this library, so the python objects can't be reconstructed",
+                self.0.name()
+            )));
+        }
+        let sorted = self.0.is_sorted_flag();
         let polars = POLARS.bind(py);
         let s = SERIES.bind(py);
-        match s
+        let pyseries = match s
             .getattr("_import_arrow_from_c")
             .or_else(|_| s.getattr("_import_from_c"))
         {
             // Go via polars
             Ok(import_arrow_from_c) => {
                 // Get supported compatibility level
-                let compat_level = CompatLevel::with_level(
-                    s.getattr("_newest_compat_level")
-                        .map_or(1, |newest_compat_level| {
-                            newest_compat_level.call0().unwrap().extract().unwrap()
-                        }),
-                )
-                .unwrap_or(CompatLevel::newest());
-                // Prepare pointers on the heap.
+                let newest = match s.getattr("_newest_compat_level") {
+                    Ok(newest_compat_level) => newest_compat_level.call0()?.extract()?,
+                    Err(_) => 1,
+                };
+                let compat_level = match compat_level {
+                    Some(level) if level.get_level() < newest => level,
+                    _ => crate::compat_level(newest),
+                };
+                ffi_trace!(
+                    "export series '{}' via polars: compat_level={}, n_chunks={}, dtype={}",
+                    self.0.name(),
+                    compat_level.get_level(),
+                    self.0.n_chunks(),
+                    self.0.dtype()
+                );
+                // The schemas are only borrowed by the other side. The arrays are released if
+                // the import fails before python took them.
+                let mut schemas = Vec::with_capacity(self.0.n_chunks());
+                let mut arrays = ExportedArrays::with_capacity(self.0.n_chunks());
                 let mut chunk_ptrs = Vec::with_capacity(self.0.n_chunks());
                 for i in 0..self.0.n_chunks() {
                     let array = self.0.to_arrow(i, compat_level);
-                    let schema = Box::new(arrow::ffi::export_field_to_c(&ArrowField::new(
-                        "".into(),
-                        array.dtype().clone(),
-                        true,
-                    )));
-                    let array = Box::new(arrow::ffi::export_array_to_c(array.clone()));
-
-                    let schema_ptr: *const arrow::ffi::ArrowSchema = Box::leak(schema);
-                    let array_ptr: *const arrow::ffi::ArrowArray = Box::leak(array);
-
-                    chunk_ptrs.push((schema_ptr as Py_uintptr_t, array_ptr as Py_uintptr_t))
+                    // Export the field with the series name. Nested struct field names are part of
+                    // the arrow dtype, so they survive the round-trip as well.
+                    let field = export_field(&self.0, &array, compat_level);
+                    let schema = Box::new(arrow::ffi::export_field_to_c(&field));
+                    let schema_ptr: *const arrow::ffi::ArrowSchema = &*schema;
+                    let array_ptr = arrays.push(array);
+                    schemas.push(schema);
+                    chunk_ptrs.push((schema_ptr as Py_uintptr_t, array_ptr as Py_uintptr_t));
                 }
 
-                // Somehow we need to clone the Vec, because pyo3 doesn't accept a slice here.
-                let pyseries = import_arrow_from_c
-                    .call1((self.0.name().as_str(), chunk_ptrs.clone()))
-                    .unwrap();
-                // Deallocate boxes
-                for (schema_ptr, array_ptr) in chunk_ptrs {
-                    let schema_ptr = schema_ptr as *mut arrow::ffi::ArrowSchema;
-                    let array_ptr = array_ptr as *mut arrow::ffi::ArrowArray;
-                    unsafe {
-                        // We can drop both because the `schema` isn't read in an owned matter on the other side.
-                        let _ = Box::from_raw(schema_ptr);
-
-                        // The array is `ptr::read_unaligned` so there are two owners.
-                        // We drop the box, and forget the content so the other process is the owner.
-                        let array = Box::from_raw(array_ptr);
-                        // We must forget because the other process will call the release callback.
-                        // Read *array as Box::into_inner
-                        let array = *array;
-                        std::mem::forget(array);
-                    }
-                }
+                let pyseries = import_arrow_from_c.call1((self.0.name().as_str(), chunk_ptrs))?;
+                // The arrays are `ptr::read_unaligned` on the other side, so there are two owners.
+                // We must forget our copies because the other side will call the release callbacks.
+                arrays.imported();
+                drop(schemas);
 
-                pyseries.to_object(py)
+                pyseries
             }
             // Go via pyarrow
             Err(_) => {
+                ffi_trace!(
+                    "export series '{}' via pyarrow: compat_level={}, n_chunks={}, dtype={}",
+                    self.0.name(),
+                    CompatLevel::oldest().get_level(),
+                    self.0.n_chunks(),
+                    self.0.dtype()
+                );
                 let s = self.0.rechunk();
                 let name = s.name().as_str();
                 let arr = s.to_arrow(0, CompatLevel::oldest());
-                let pyarrow = py.import_bound("pyarrow").expect("pyarrow not installed");
+                let field = export_field(&s, &arr, CompatLevel::oldest());
+                let via_pyarrow = || {
+                    let pyarrow = pyarrow(py)?;
+                    let arg = to_py_array(arr, &field, py, pyarrow)?;
+                    let s = polars.call_method1("from_arrow", (arg,))?;
+                    s.call_method1("rename", (name,))
+                };
+                via_pyarrow().map_err(|err| missing_import_error(py, err))?
+            }
+        };
 
-                let arg = to_py_array(arr, py, pyarrow).unwrap();
-                let s = polars.call_method1("from_arrow", (arg,)).unwrap();
-                let s = s.call_method1("rename", (name,)).unwrap();
-                s.to_object(py)
+        // Restore the sorted flag, so that python polars can keep using its fast paths.
+        let pyseries = match sorted {
+            IsSorted::Not => pyseries,
+            sorted => {
+                let descending = sorted == IsSorted::Descending;
+                let kwargs = [("descending", descending)].into_py_dict_bound(py);
+                pyseries.call_method(intern!(py, "set_sorted"), (), Some(&kwargs))?
             }
-        }
+        };
+        Ok(pyseries.into_py(py))
     }
 }
 
-impl IntoPy<PyObject> for PyDataFrame {
+/// The error for a python polars that can't import a series from rust.
+///
+/// Very old python polars versions have neither `Series._import_arrow_from_c` nor
+/// `Series._import_from_c`, so the series is passed via pyarrow, which can fail as well, e.g.
+/// if pyarrow isn't installed.
+fn missing_import_error(py: Python<'_>, err: PyErr) -> PyErr {
+    let installed =
+        python_polars_version(py).unwrap_or_else(|| "the installed python polars".to_string());
+    let out = PyRuntimeError::new_err(format!(
+        "cannot convert a series to python: {installed} doesn't support importing arrow data \
+        from rust (`Series._import_arrow_from_c` is missing) and the pyarrow fallback failed: \
+        {err}. This library is built against rust polars {}, upgrade python polars to at least \
+        that version.",
+        polars::VERSION
+    ));
+    out.set_cause(py, Some(err));
+    out
+}
+
+impl IntoPy<PyObject> for PySeries {
     fn into_py(self, py: Python<'_>) -> PyObject {
+        self.try_into_py(py)
+            .expect("could not convert the series to python, use `PySeries::try_into_py` to handle this error")
+    }
+}
+
+/// Series with more chunks than this are rechunked before they are passed to python.
+const MAX_EXPORTED_CHUNKS: usize = 1024;
+
+/// Frames with more columns than this are passed to python as a single arrow C stream.
+///
+/// Narrower frames are passed as a python series per column, which python polars collects
+/// into a frame with a python loop. `make bench` in `python_tests` times both paths for frames
+/// of up to 50k columns.
+const WIDE_FRAME_COLUMNS: usize = 1_000;
+
+/// A frame that python polars builds from its `__arrow_c_stream__`.
+#[pyclass(name = "DataFrameStream", module = "pyo3_polars")]
+struct DataFrameStream(PyDataFrame);
+
+#[pymethods]
+impl DataFrameStream {
+    #[pyo3(signature = (requested_schema=None))]
+    fn __arrow_c_stream__<'py>(
+        &self,
+        py: Python<'py>,
+        requested_schema: Option<Bound<'py, PyAny>>,
+    ) -> PyResult<Bound<'py, PyCapsule>> {
+        self.0.__arrow_c_stream__(py, requested_schema)
+    }
+}
+
+impl PyDataFrame {
+    /// Convert to a python polars `DataFrame`.
+    ///
+    /// Unlike [`IntoPy::into_py`] this raises a python exception instead of panicking.
+    pub fn try_into_py(self, py: Python<'_>) -> PyResult<PyObject> {
+        let (height, width) = self.0.shape();
+        // The categories of categoricals and python objects are only kept per series.
+        let as_stream = width > WIDE_FRAME_COLUMNS
+            && self.0.get_columns().iter().all(|c| {
+                let dtype = c.dtype();
+                !dtype.contains_categoricals() && !dtype.contains_objects()
+            });
+        let df_object = if as_stream {
+            match self.clone().export_stream(py) {
+                Ok(df_object) => df_object,
+                // Python polars versions without the arrow PyCapsule interface reject the stream.
+                Err(err) if err.is_instance_of::<PyTypeError>(py) => self.export_series(py)?,
+                Err(err) => return Err(err),
+            }
+        } else {
+            self.export_series(py)?
+        };
+
+        // Every column carries its dtype, also without any rows, so the frame is rebuilt
+        // column by column. Check that python didn't reshape it, e.g. by broadcasting.
+        // A frame without columns has no height in python polars.
+        if width > 0 {
+            let shape = df_object
+                .getattr(intern!(py, "shape"))?
+                .extract::<(usize, usize)>()?;
+            if shape != (height, width) {
+                return Err(PyPolarsErr::from(polars_err!(
+                    ShapeMismatch: "exported a frame of shape {:?}, but python polars built a frame of shape {:?}",
+                    (height, width),
+                    shape
+                ))
+                .into());
+            }
+        }
+        Ok(df_object.into_py(py))
+    }
+
+    /// Build the python frame from a python series per column.
+    fn export_series(self, py: Python<'_>) -> PyResult<Bound<'_, PyAny>> {
         let pyseries = self
             .0
             .get_columns()
             .iter()
-            .map(|s| PySeries(s.as_materialized_series().clone()).into_py(py))
+            .map(|s| PySeries(s.as_materialized_series().clone()).try_into_py(py))
+            .collect::<PyResult<Vec<_>>>()?;
+        POLARS
+            .bind(py)
+            .call_method1(intern!(py, "DataFrame"), (pyseries,))
+    }
+
+    /// Build the python frame from a single arrow C stream.
+    fn export_stream(self, py: Python<'_>) -> PyResult<Bound<'_, PyAny>> {
+        let polars = POLARS.bind(py);
+        // Arrow has no sorted flags, so they are set again in python.
+        let sorted = self
+            .0
+            .get_columns()
+            .iter()
+            .filter_map(|c| match c.as_materialized_series().is_sorted_flag() {
+                IsSorted::Not => None,
+                sorted => Some((c.name().to_string(), sorted == IsSorted::Descending)),
+            })
             .collect::<Vec<_>>();
 
+        let df_object = polars.call_method1(intern!(py, "DataFrame"), (DataFrameStream(self),))?;
+        if sorted.is_empty() {
+            return Ok(df_object);
+        }
+        let exprs = sorted
+            .into_iter()
+            .map(|(name, descending)| {
+                let kwargs = [("descending", descending)].into_py_dict_bound(py);
+                polars
+                    .call_method1(intern!(py, "col"), (name,))?
+                    .call_method(intern!(py, "set_sorted"), (), Some(&kwargs))
+            })
+            .collect::<PyResult<Vec<_>>>()?;
+        df_object.call_method1(intern!(py, "with_columns"), (exprs,))
+    }
+}
+
+impl IntoPy<PyObject> for PyDataFrame {
+    fn into_py(self, py: Python<'_>) -> PyObject {
+        self.try_into_py(py)
+            .expect("could not convert the frame to python, use `PyDataFrame::try_into_py` to handle this error")
+    }
+}
+
+#[cfg(feature = "lazy")]
+impl PyLazyFrame {
+    /// Convert to a python polars `LazyFrame`.
+    ///
+    /// Unlike [`IntoPy::into_py`] this raises a python exception instead of panicking.
+    pub fn try_into_py(self, py: Python<'_>) -> PyResult<PyObject> {
+        // A plan that only scans an in-memory frame is sent through arrow FFI, serializing
+        // would copy the data.
+        if let DslPlan::DataFrameScan { df, .. } = &self.0.logical_plan {
+            let df = PyDataFrame(df.as_ref().clone()).try_into_py(py)?;
+            return Ok(df.call_method0(py, intern!(py, "lazy"))?);
+        }
+        // A plan that reads from a rust `AnonymousScan` can't be serialized, so it is kept in
+        // rust and python polars collects it through an io source.
+        if crate::scan::has_anonymous_scan(&self.0.logical_plan) {
+            return crate::scan::register_io_source(py, self.0);
+        }
+
         let polars = POLARS.bind(py);
-        let df_object = polars.call_method1("DataFrame", (pyseries,)).unwrap();
-        df_object.into_py(py)
+        let cls = polars.getattr("LazyFrame")?;
+        let instance = cls.call_method1(intern!(py, "__new__"), (&cls,))?;
+        let writer = self.serialize(SerializeFormat::Binary)?;
+
+        instance.call_method1("__setstate__", (&*writer,))?;
+        Ok(instance.into_py(py))
     }
 }
 
 #[cfg(feature = "lazy")]
 impl IntoPy<PyObject> for PyLazyFrame {
     fn into_py(self, py: Python<'_>) -> PyObject {
+        self.try_into_py(py)
+            .expect("could not convert the lazy frame to python, use `PyLazyFrame::try_into_py` to handle this error")
+    }
+}
+
+#[cfg(feature = "expr")]
+impl PyExpr {
+    /// Convert to a python polars `Expr`.
+    ///
+    /// Unlike [`IntoPy::into_py`] this raises a python exception instead of panicking.
+    pub fn try_into_py(self, py: Python<'_>) -> PyResult<PyObject> {
         let polars = POLARS.bind(py);
-        let cls = polars.getattr("LazyFrame").unwrap();
-        let instance = cls.call_method1(intern!(py, "__new__"), (&cls,)).unwrap();
+        let cls = polars.getattr("Expr")?;
+        let instance = cls.call_method1(intern!(py, "__new__"), (&cls,))?;
         let mut writer: Vec<u8> = vec![];
-        ciborium::ser::into_writer(&self.0.logical_plan, &mut writer).unwrap();
+        ciborium::ser::into_writer(&self.0, &mut writer)
+            .map_err(|e| PyPolarsErr::Other(format!("{}", e)))?;
+
+        instance.call_method1("__setstate__", (&*writer,))?;
+        Ok(instance.into_py(py))
+    }
+
+    /// The names of the columns the expression reads, in the order they appear.
+    ///
+    /// Every name is listed once. Wildcards and selectors like `pl.all()` aren't columns and
+    /// are left out.
+    pub fn root_names(&self) -> Vec<String> {
+        let mut names = Vec::<String>::new();
+        for name in polars_plan::utils::expr_to_leaf_column_names_iter(&self.0) {
+            if !names.iter().any(|seen| seen == name.as_str()) {
+                names.push(name.into_string());
+            }
+        }
+        names
+    }
 
-        instance.call_method1("__setstate__", (&*writer,)).unwrap();
-        instance.into_py(py)
+    /// The dtype of the output of the expression, when it is evaluated on a frame with
+    /// `schema`.
+    ///
+    /// Raises the polars error of the expression if it doesn't fit the schema, e.g. a
+    /// `ColumnNotFoundError` for a column that isn't in the schema.
+    pub fn output_dtype(&self, schema: &PySchema) -> PyResult<PyDataType> {
+        let field = self
+            .0
+            .to_field(&schema.0, polars_plan::plans::Context::Default)
+            .map_err(PyPolarsErr::from)?;
+        Ok(PyDataType(field.dtype))
     }
 }
 
-#[cfg(feature = "lazy")]
+#[cfg(feature = "expr")]
 impl IntoPy<PyObject> for PyExpr {
     fn into_py(self, py: Python<'_>) -> PyObject {
-        let polars = POLARS.bind(py);
-        let cls = polars.getattr("Expr").unwrap();
-        let instance = cls.call_method1(intern!(py, "__new__"), (&cls,)).unwrap();
-        let mut writer: Vec<u8> = vec![];
-        ciborium::ser::into_writer(&self.0, &mut writer).unwrap();
-
-        instance.call_method1("__setstate__", (&*writer,)).unwrap();
-        instance.into_py(py)
+        self.try_into_py(py)
+            .expect("could not convert the expression to python, use `PyExpr::try_into_py` to handle this error")
     }
 }
 
@@ -354,149 +2158,220 @@ pub(crate) fn to_series(py: Python, s: PySeries) -> PyObject {
     constructor.call1((s,)).unwrap().into_py(py)
 }
 
-impl ToPyObject for PyDataType {
-    fn to_object(&self, py: Python) -> PyObject {
+impl PyDataType {
+    /// Parse the name of a polars dtype, e.g. `"Int32"`.
+    ///
+    /// Parametric dtypes take their parameters in brackets, like the `repr` of a python polars
+    /// dtype: `"Datetime[us, UTC]"`, `"Duration[ms]"`, `"Decimal[10, 2]"`, `"List[String]"` or
+    /// `"Array[Float64, 3]"`. Without parameters the defaults of the python polars class are
+    /// used. The [`FromPyObject`] implementation accepts such names as well, so a
+    /// `#[pyfunction]` can take a dtype as a string.
+    pub fn from_name(name: &str) -> PyResult<Self> {
+        let name = name.trim();
+        let Some((base, params)) = name.split_once('[') else {
+            return dtype_from_class_name(name).map(PyDataType);
+        };
+        let params = params
+            .strip_suffix(']')
+            .ok_or_else(|| unknown_dtype_error(name))?;
+        let params = split_dtype_params(params);
+        let parse_int = |param: &str| {
+            param.parse::<usize>().map_err(|_| {
+                PyValueError::new_err(format!(
+                    "expected an integer parameter in '{name}', got '{param}'"
+                ))
+            })
+        };
+        let dtype = match (base.trim(), params.as_slice()) {
+            ("Datetime", [time_unit]) => DataType::Datetime(PyTimeUnit::parse(time_unit)?.0, None),
+            ("Datetime", [time_unit, tz]) => {
+                validate_time_zone(tz)?;
+                DataType::Datetime(PyTimeUnit::parse(time_unit)?.0, Some((*tz).into()))
+            }
+            ("Duration", [time_unit]) => DataType::Duration(PyTimeUnit::parse(time_unit)?.0),
+            #[cfg(feature = "dtype-decimal")]
+            ("Decimal", [precision, scale]) => {
+                decimal_dtype(Some(parse_int(precision)? as i64), parse_int(scale)? as i64)?
+            }
+            ("List", [inner]) => DataType::List(Box::new(PyDataType::from_name(inner)?.0)),
+            #[cfg(feature = "dtype-array")]
+            ("Array", [inner, size]) => {
+                DataType::Array(Box::new(PyDataType::from_name(inner)?.0), parse_int(size)?)
+            }
+            _ => return Err(unknown_dtype_error(name)),
+        };
+        Ok(PyDataType(dtype))
+    }
+
+    /// Convert to a python polars dtype.
+    ///
+    /// Unlike [`ToPyObject::to_object`] this raises a python exception instead of panicking.
+    pub fn try_to_object(&self, py: Python<'_>) -> PyResult<PyObject> {
         let pl = POLARS.bind(py);
 
-        match &self.0 {
+        let dtype: PyObject = match &self.0 {
             DataType::Int8 => {
-                let class = pl.getattr(intern!(py, "Int8")).unwrap();
-                class.call0().unwrap().into()
+                let class = pl.getattr(intern!(py, "Int8"))?;
+                class.call0()?.into()
             }
             DataType::Int16 => {
-                let class = pl.getattr(intern!(py, "Int16")).unwrap();
-                class.call0().unwrap().into()
+                let class = pl.getattr(intern!(py, "Int16"))?;
+                class.call0()?.into()
             }
             DataType::Int32 => {
-                let class = pl.getattr(intern!(py, "Int32")).unwrap();
-                class.call0().unwrap().into()
+                let class = pl.getattr(intern!(py, "Int32"))?;
+                class.call0()?.into()
             }
             DataType::Int64 => {
-                let class = pl.getattr(intern!(py, "Int64")).unwrap();
-                class.call0().unwrap().into()
+                let class = pl.getattr(intern!(py, "Int64"))?;
+                class.call0()?.into()
+            }
+            #[cfg(feature = "dtype-i128")]
+            DataType::Int128 => {
+                let class = pl.getattr(intern!(py, "Int128"))?;
+                class.call0()?.into()
             }
             DataType::UInt8 => {
-                let class = pl.getattr(intern!(py, "UInt8")).unwrap();
-                class.call0().unwrap().into()
+                let class = pl.getattr(intern!(py, "UInt8"))?;
+                class.call0()?.into()
             }
             DataType::UInt16 => {
-                let class = pl.getattr(intern!(py, "UInt16")).unwrap();
-                class.call0().unwrap().into()
+                let class = pl.getattr(intern!(py, "UInt16"))?;
+                class.call0()?.into()
             }
             DataType::UInt32 => {
-                let class = pl.getattr(intern!(py, "UInt32")).unwrap();
-                class.call0().unwrap().into()
+                let class = pl.getattr(intern!(py, "UInt32"))?;
+                class.call0()?.into()
             }
             DataType::UInt64 => {
-                let class = pl.getattr(intern!(py, "UInt64")).unwrap();
-                class.call0().unwrap().into()
+                let class = pl.getattr(intern!(py, "UInt64"))?;
+                class.call0()?.into()
             }
             DataType::Float32 => {
-                let class = pl.getattr(intern!(py, "Float32")).unwrap();
-                class.call0().unwrap().into()
+                let class = pl.getattr(intern!(py, "Float32"))?;
+                class.call0()?.into()
             }
             DataType::Float64 | DataType::Unknown(UnknownKind::Float) => {
-                let class = pl.getattr(intern!(py, "Float64")).unwrap();
-                class.call0().unwrap().into()
+                let class = pl.getattr(intern!(py, "Float64"))?;
+                class.call0()?.into()
             }
             #[cfg(feature = "dtype-decimal")]
             DataType::Decimal(precision, scale) => {
-                let class = pl.getattr(intern!(py, "Decimal")).unwrap();
+                let class = pl.getattr(intern!(py, "Decimal"))?;
                 let args = (*precision, *scale);
-                class.call1(args).unwrap().into()
+                class.call1(args)?.into()
             }
             DataType::Boolean => {
-                let class = pl.getattr(intern!(py, "Boolean")).unwrap();
-                class.call0().unwrap().into()
+                let class = pl.getattr(intern!(py, "Boolean"))?;
+                class.call0()?.into()
             }
             DataType::String | DataType::Unknown(UnknownKind::Str) => {
-                let class = pl.getattr(intern!(py, "String")).unwrap();
-                class.call0().unwrap().into()
+                let class = pl.getattr(intern!(py, "String"))?;
+                class.call0()?.into()
             }
-            DataType::Binary => {
-                let class = pl.getattr(intern!(py, "Binary")).unwrap();
-                class.call0().unwrap().into()
+            // Python only knows the view representation, the logical type is the same.
+            DataType::Binary | DataType::BinaryOffset => {
+                let class = pl.getattr(intern!(py, "Binary"))?;
+                class.call0()?.into()
             }
             #[cfg(feature = "dtype-array")]
             DataType::Array(inner, size) => {
-                let class = pl.getattr(intern!(py, "Array")).unwrap();
-                let inner = PyDataType(*inner.clone()).to_object(py);
+                let class = pl.getattr(intern!(py, "Array"))?;
+                let inner = PyDataType(*inner.clone()).try_to_object(py)?;
                 let args = (inner, *size);
-                class.call1(args).unwrap().into()
+                class.call1(args)?.into()
             }
             DataType::List(inner) => {
-                let class = pl.getattr(intern!(py, "List")).unwrap();
-                let inner = PyDataType(*inner.clone()).to_object(py);
-                class.call1((inner,)).unwrap().into()
+                let class = pl.getattr(intern!(py, "List"))?;
+                let inner = PyDataType(*inner.clone()).try_to_object(py)?;
+                class.call1((inner,))?.into()
             }
             DataType::Date => {
-                let class = pl.getattr(intern!(py, "Date")).unwrap();
-                class.call0().unwrap().into()
+                let class = pl.getattr(intern!(py, "Date"))?;
+                class.call0()?.into()
             }
             DataType::Datetime(tu, tz) => {
-                let datetime_class = pl.getattr(intern!(py, "Datetime")).unwrap();
+                let datetime_class = pl.getattr(intern!(py, "Datetime"))?;
                 datetime_class
-                    .call1((tu.to_ascii(), tz.as_ref().map(|s| s.as_str())))
-                    .unwrap()
+                    .call1((tu.to_ascii(), tz.as_ref().map(|s| s.as_str())))?
                     .into()
             }
             DataType::Duration(tu) => {
-                let duration_class = pl.getattr(intern!(py, "Duration")).unwrap();
-                duration_class.call1((tu.to_ascii(),)).unwrap().into()
+                let duration_class = pl.getattr(intern!(py, "Duration"))?;
+                duration_class.call1((tu.to_ascii(),))?.into()
             }
             #[cfg(feature = "object")]
             DataType::Object(_, _) => {
-                let class = pl.getattr(intern!(py, "Object")).unwrap();
-                class.call0().unwrap().into()
+                let class = pl.getattr(intern!(py, "Object"))?;
+                class.call0()?.into()
             }
             #[cfg(feature = "dtype-categorical")]
             DataType::Categorical(_, ordering) => {
-                let class = pl.getattr(intern!(py, "Categorical")).unwrap();
+                let class = pl.getattr(intern!(py, "Categorical"))?;
                 let ordering = match ordering {
                     CategoricalOrdering::Physical => "physical",
                     CategoricalOrdering::Lexical => "lexical",
                 };
-                class.call1((ordering,)).unwrap().into()
+                class.call1((ordering,))?.into()
             }
             #[cfg(feature = "dtype-categorical")]
             DataType::Enum(rev_map, _) => {
                 // we should always have an initialized rev_map coming from rust
-                let categories = rev_map.as_ref().unwrap().get_categories();
-                let class = pl.getattr(intern!(py, "Enum")).unwrap();
-                let s = Series::from_arrow("category".into(), categories.clone().boxed()).unwrap();
+                let categories = rev_map.as_ref().expect("rev_map").get_categories();
+                let class = pl.getattr(intern!(py, "Enum"))?;
+                let s = Series::from_arrow("category".into(), categories.clone().boxed())
+                    .map_err(PyPolarsErr::from)?;
                 let series = to_series(py, PySeries(s));
-                return class.call1((series,)).unwrap().into();
+                return Ok(class.call1((series,))?.into());
             }
-            DataType::Time => pl.getattr(intern!(py, "Time")).unwrap().into(),
+            DataType::Time => pl.getattr(intern!(py, "Time"))?.into(),
             #[cfg(feature = "dtype-struct")]
             DataType::Struct(fields) => {
-                let field_class = pl.getattr(intern!(py, "Field")).unwrap();
-                let iter = fields.iter().map(|fld| {
-                    let name = fld.name().as_str();
-                    let dtype = PyDataType(fld.dtype().clone()).to_object(py);
-                    field_class.call1((name, dtype)).unwrap()
-                });
-                let fields = PyList::new_bound(py, iter);
-                let struct_class = pl.getattr(intern!(py, "Struct")).unwrap();
-                struct_class.call1((fields,)).unwrap().into()
+                let field_class = pl.getattr(intern!(py, "Field"))?;
+                let fields = fields
+                    .iter()
+                    .map(|fld| {
+                        let name = fld.name().as_str();
+                        let dtype = PyDataType(fld.dtype().clone()).try_to_object(py)?;
+                        field_class.call1((name, dtype))
+                    })
+                    .collect::<PyResult<Vec<_>>>()?;
+                let fields = PyList::new_bound(py, fields);
+                let struct_class = pl.getattr(intern!(py, "Struct"))?;
+                struct_class.call1((fields,))?.into()
             }
             DataType::Null => {
-                let class = pl.getattr(intern!(py, "Null")).unwrap();
-                class.call0().unwrap().into()
+                let class = pl.getattr(intern!(py, "Null"))?;
+                class.call0()?.into()
             }
             DataType::Unknown(UnknownKind::Int(v)) => {
-                PyDataType(materialize_dyn_int(*v).dtype()).to_object(py)
+                PyDataType(materialize_dyn_int(*v).dtype()).try_to_object(py)?
             }
             DataType::Unknown(_) => {
-                let class = pl.getattr(intern!(py, "Unknown")).unwrap();
-                class.call0().unwrap().into()
-            }
-            DataType::BinaryOffset => {
-                panic!("this type isn't exposed to python")
+                let class = pl.getattr(intern!(py, "Unknown"))?;
+                class.call0()?.into()
             }
             #[allow(unreachable_patterns)]
-            _ => panic!("activate dtype"),
-        }
+            dt => {
+                return Err(PyTypeError::new_err(format!(
+                    "cannot convert '{dt}' to python, the plugin isn't compiled with the right features",
+                )))
+            }
+        };
+        Ok(dtype)
+    }
+}
+
+impl ToPyObject for PyDataType {
+    fn to_object(&self, py: Python) -> PyObject {
+        self.try_to_object(py)
+            .expect("could not convert the dtype to python, use `PyDataType::try_to_object` to handle this error")
+    }
+}
+
+impl IntoPy<PyObject> for PyDataType {
+    fn into_py(self, py: Python<'_>) -> PyObject {
+        self.to_object(py)
     }
 }
 
@@ -510,6 +2385,65 @@ impl IntoPy<PyObject> for PySchema {
     }
 }
 
+impl PyField {
+    /// Convert to a python polars `Field`.
+    ///
+    /// Unlike [`IntoPy::into_py`] this raises a python exception instead of panicking.
+    pub fn try_into_py(self, py: Python<'_>) -> PyResult<PyObject> {
+        let field_class = POLARS.bind(py).getattr(intern!(py, "Field"))?;
+        let dtype = PyDataType(self.0.dtype).try_to_object(py)?;
+        let field = field_class.call1((self.0.name.as_str(), dtype))?;
+        Ok(field.into_py(py))
+    }
+}
+
+impl IntoPy<PyObject> for PyField {
+    fn into_py(self, py: Python<'_>) -> PyObject {
+        self.try_into_py(py)
+            .expect("could not convert the field to python, use `PyField::try_into_py` to handle this error")
+    }
+}
+
+impl<'py> FromPyObject<'py> for PySchema {
+    /// Extract a schema from a `dict[str, DataType]`, a polars `Schema` or a list of polars
+    /// `Field`s.
+    ///
+    /// The fields are added in iteration order of the mapping, which for `dict` is the
+    /// insertion order, so the column order is preserved.
+    fn extract_bound(ob: &Bound<'py, PyAny>) -> PyResult<Self> {
+        if let Ok(fields) = ob.downcast::<PyList>() {
+            let mut schema = Schema::with_capacity(fields.len());
+            for field in fields.iter() {
+                let field = field.extract::<PyField>()?.0;
+                if schema.contains(field.name()) {
+                    return Err(PyPolarsErr::from(polars_err!(
+                        Duplicate: "field '{}' occurs more than once in the schema", field.name()
+                    ))
+                    .into());
+                }
+                schema.with_column(field.name, field.dtype);
+            }
+            return Ok(PySchema(Arc::new(schema)));
+        }
+
+        let items = ob.call_method0(intern!(ob.py(), "items"))?;
+        let mut schema = Schema::with_capacity(items.len().unwrap_or(0));
+        for item in items.iter()? {
+            let (name, dtype) = item?.extract::<(PyBackedStr, PyDataType)>()?;
+            let name: &str = name.as_ref();
+            schema.with_column(name.into(), dtype.0);
+        }
+        Ok(PySchema(Arc::new(schema)))
+    }
+}
+
+/// The name python polars registers its `Object` dtype under.
+///
+/// The registry of the objects lives in python polars and can't be shared with a plugin, so
+/// an extracted `Object` dtype never carries one.
+#[cfg(feature = "object")]
+const OBJECT_NAME: &str = "object";
+
 impl<'py> FromPyObject<'py> for PyDataType {
     fn extract_bound(ob: &Bound<'py, PyAny>) -> PyResult<Self> {
         let py = ob.py();
@@ -522,50 +2456,16 @@ impl<'py> FromPyObject<'py> for PyDataType {
                     .getattr(intern!(py, "__name__"))?
                     .str()?
                     .extract::<PyBackedStr>()?;
-                match &*name {
-                    "Int8" => DataType::Int8,
-                    "Int16" => DataType::Int16,
-                    "Int32" => DataType::Int32,
-                    "Int64" => DataType::Int64,
-                    "UInt8" => DataType::UInt8,
-                    "UInt16" => DataType::UInt16,
-                    "UInt32" => DataType::UInt32,
-                    "UInt64" => DataType::UInt64,
-                    "Float32" => DataType::Float32,
-                    "Float64" => DataType::Float64,
-                    "Boolean" => DataType::Boolean,
-                    "String" => DataType::String,
-                    "Binary" => DataType::Binary,
-                    #[cfg(feature = "dtype-categorical")]
-                    "Categorical" => DataType::Categorical(None, Default::default()),
-                    #[cfg(feature = "dtype-categorical")]
-                    "Enum" => DataType::Enum(None, Default::default()),
-                    "Date" => DataType::Date,
-                    "Time" => DataType::Time,
-                    "Datetime" => DataType::Datetime(TimeUnit::Microseconds, None),
-                    "Duration" => DataType::Duration(TimeUnit::Microseconds),
-                    #[cfg(feature = "dtype-decimal")]
-                    "Decimal" => DataType::Decimal(None, None), // "none" scale => "infer"
-                    "List" => DataType::List(Box::new(DataType::Null)),
-                    #[cfg(feature = "dtype-array")]
-                    "Array" => DataType::Array(Box::new(DataType::Null), 0),
-                    #[cfg(feature = "dtype-struct")]
-                    "Struct" => DataType::Struct(vec![]),
-                    "Null" => DataType::Null,
-                    #[cfg(feature = "object")]
-                    "Object" => todo!(),
-                    "Unknown" => DataType::Unknown(Default::default()),
-                    dt => {
-                        return Err(PyTypeError::new_err(format!(
-                            "'{dt}' is not a Polars data type, or the plugin isn't compiled with the right features",
-                        )))
-                    },
-                }
-            },
+                dtype_from_class_name(&name)?
+            }
+            // A name like `"Int64"` or `"Datetime[us, UTC]"`.
+            "str" => PyDataType::from_name(&ob.extract::<PyBackedStr>()?)?.0,
             "Int8" => DataType::Int8,
             "Int16" => DataType::Int16,
             "Int32" => DataType::Int32,
             "Int64" => DataType::Int64,
+            #[cfg(feature = "dtype-i128")]
+            "Int128" => DataType::Int128,
             "UInt8" => DataType::UInt8,
             "UInt16" => DataType::UInt16,
             "UInt32" => DataType::UInt32,
@@ -579,25 +2479,40 @@ impl<'py> FromPyObject<'py> for PyDataType {
             "Categorical" => {
                 let ordering = ob.getattr(intern!(py, "ordering")).unwrap();
                 let ordering = ordering.extract::<PyBackedStr>()?;
-                let ordering = match  ordering.as_bytes() {
+                let ordering = match ordering.as_bytes() {
                     b"physical" => CategoricalOrdering::Physical,
                     b"lexical" => CategoricalOrdering::Lexical,
                     ordering => {
                         let ordering = std::str::from_utf8(ordering).unwrap();
-                        return Err(PyValueError::new_err(format!("invalid ordering argument: {ordering}")))
+                        return Err(PyValueError::new_err(format!(
+                            "invalid ordering argument: {ordering}"
+                        )));
                     }
                 };
-
-                DataType::Categorical(None, ordering)
-            },
+                // Keep the categories if the dtype carries them as a `Series`, so that the codes
+                // stay aligned with the categories of the python side.
+                let rev_map = match ob.getattr(intern!(py, "categories")) {
+                    Ok(categories) if categories.hasattr(intern!(py, "_s"))? => {
+                        let s = get_series(&categories)?;
+                        let ca = s.str().map_err(PyPolarsErr::from)?.rechunk();
+                        let categories = ca.downcast_iter().next().unwrap().clone();
+                        Some(Arc::new(RevMapping::build_local(categories)))
+                    }
+                    _ => None,
+                };
+                DataType::Categorical(rev_map, ordering)
+            }
             #[cfg(feature = "dtype-categorical")]
             "Enum" => {
                 let categories = ob.getattr(intern!(py, "categories")).unwrap();
                 let s = get_series(&categories.as_borrowed())?;
                 let ca = s.str().map_err(PyPolarsErr::from)?;
                 let categories = ca.downcast_iter().next().unwrap().clone();
-                DataType::Enum(Some(Arc::new(RevMapping::build_local(categories))), Default::default())
-            },
+                DataType::Enum(
+                    Some(Arc::new(RevMapping::build_local(categories))),
+                    Default::default(),
+                )
+            }
             "Date" => DataType::Date,
             "Time" => DataType::Time,
             "Datetime" => {
@@ -605,24 +2520,27 @@ impl<'py> FromPyObject<'py> for PyDataType {
                 let time_unit = time_unit.extract::<PyTimeUnit>()?.0;
                 let time_zone = ob.getattr(intern!(py, "time_zone")).unwrap();
                 let time_zone: Option<String> = time_zone.extract()?;
+                if let Some(tz) = &time_zone {
+                    validate_time_zone(tz)?;
+                }
                 DataType::Datetime(time_unit, time_zone.map(PlSmallStr::from))
-            },
+            }
             "Duration" => {
                 let time_unit = ob.getattr(intern!(py, "time_unit")).unwrap();
                 let time_unit = time_unit.extract::<PyTimeUnit>()?.0;
                 DataType::Duration(time_unit)
-            },
+            }
             #[cfg(feature = "dtype-decimal")]
             "Decimal" => {
                 let precision = ob.getattr(intern!(py, "precision"))?.extract()?;
                 let scale = ob.getattr(intern!(py, "scale"))?.extract()?;
-                DataType::Decimal(precision, Some(scale))
-            },
+                decimal_dtype(precision, scale)?
+            }
             "List" => {
                 let inner = ob.getattr(intern!(py, "inner")).unwrap();
                 let inner = inner.extract::<PyDataType>()?;
                 DataType::List(Box::new(inner.0))
-            },
+            }
             #[cfg(feature = "dtype-array")]
             "Array" => {
                 let inner = ob.getattr(intern!(py, "inner")).unwrap();
@@ -630,7 +2548,7 @@ impl<'py> FromPyObject<'py> for PyDataType {
                 let inner = inner.extract::<PyDataType>()?;
                 let size = size.extract::<usize>()?;
                 DataType::Array(Box::new(inner.0), size)
-            },
+            }
             #[cfg(feature = "dtype-struct")]
             "Struct" => {
                 let fields = ob.getattr(intern!(py, "fields"))?;
@@ -640,17 +2558,407 @@ impl<'py> FromPyObject<'py> for PyDataType {
                     .map(|f| f.0)
                     .collect::<Vec<Field>>();
                 DataType::Struct(fields)
-            },
+            }
             "Null" => DataType::Null,
             #[cfg(feature = "object")]
-            "Object" => panic!("object not supported"),
+            "Object" => DataType::Object(OBJECT_NAME, None),
             "Unknown" => DataType::Unknown(Default::default()),
-            dt => {
-                return Err(PyTypeError::new_err(format!(
-                    "'{dt}' is not a Polars data type, or the plugin isn't compiled with the right features",
-                )))
-            },
+            dt => return Err(unknown_dtype_error(dt)),
         };
         Ok(PyDataType(dtype))
     }
 }
+
+fn unknown_dtype_error(name: &str) -> PyErr {
+    PyTypeError::new_err(format!(
+        "'{name}' is not a Polars data type, or the plugin isn't compiled with the right features",
+    ))
+}
+
+/// The dtype of a polars dtype class, without parameters, e.g. `pl.Datetime`.
+fn dtype_from_class_name(name: &str) -> PyResult<DataType> {
+    let dtype = match name {
+        "Int8" => DataType::Int8,
+        "Int16" => DataType::Int16,
+        "Int32" => DataType::Int32,
+        "Int64" => DataType::Int64,
+        #[cfg(feature = "dtype-i128")]
+        "Int128" => DataType::Int128,
+        "UInt8" => DataType::UInt8,
+        "UInt16" => DataType::UInt16,
+        "UInt32" => DataType::UInt32,
+        "UInt64" => DataType::UInt64,
+        "Float32" => DataType::Float32,
+        "Float64" => DataType::Float64,
+        "Boolean" => DataType::Boolean,
+        "String" => DataType::String,
+        "Binary" => DataType::Binary,
+        #[cfg(feature = "dtype-categorical")]
+        "Categorical" => DataType::Categorical(None, Default::default()),
+        #[cfg(feature = "dtype-categorical")]
+        "Enum" => DataType::Enum(None, Default::default()),
+        "Date" => DataType::Date,
+        "Time" => DataType::Time,
+        "Datetime" => DataType::Datetime(TimeUnit::Microseconds, None),
+        "Duration" => DataType::Duration(TimeUnit::Microseconds),
+        #[cfg(feature = "dtype-decimal")]
+        "Decimal" => DataType::Decimal(None, None), // "none" scale => "infer"
+        "List" => DataType::List(Box::new(DataType::Null)),
+        #[cfg(feature = "dtype-array")]
+        "Array" => DataType::Array(Box::new(DataType::Null), 0),
+        #[cfg(feature = "dtype-struct")]
+        "Struct" => DataType::Struct(vec![]),
+        "Null" => DataType::Null,
+        #[cfg(feature = "object")]
+        "Object" => DataType::Object(OBJECT_NAME, None),
+        "Unknown" => DataType::Unknown(Default::default()),
+        dt => return Err(unknown_dtype_error(dt)),
+    };
+    Ok(dtype)
+}
+
+/// The largest precision of a polars `Decimal`, the number of digits an `i128` can hold.
+#[cfg(feature = "dtype-decimal")]
+const MAX_DECIMAL_PRECISION: i64 = 38;
+
+/// A `Decimal` dtype, raising a `ValueError` for a precision or scale that polars can't
+/// represent, instead of silently producing wrong values.
+#[cfg(feature = "dtype-decimal")]
+fn decimal_dtype(precision: Option<i64>, scale: i64) -> PyResult<DataType> {
+    if let Some(precision) = precision {
+        if !(1..=MAX_DECIMAL_PRECISION).contains(&precision) {
+            return Err(PyValueError::new_err(format!(
+                "decimal precision must be between 1 and {MAX_DECIMAL_PRECISION}, got \
+                {precision}: polars stores decimals as 128 bit integers, which can't hold more \
+                digits"
+            )));
+        }
+    }
+    let max_scale = precision.unwrap_or(MAX_DECIMAL_PRECISION);
+    if !(0..=max_scale).contains(&scale) {
+        return Err(PyValueError::new_err(format!(
+            "decimal scale must be between 0 and the precision ({max_scale}), got {scale}"
+        )));
+    }
+    Ok(DataType::Decimal(
+        precision.map(|precision| precision as usize),
+        Some(scale as usize),
+    ))
+}
+
+/// Raise a `ValueError` for a time zone that polars doesn't know, if the `timezones` feature
+/// is active.
+fn validate_time_zone(tz: &str) -> PyResult<()> {
+    #[cfg(feature = "timezones")]
+    polars_core::chunked_array::temporal::validate_time_zone(tz).map_err(|_| {
+        PyValueError::new_err(format!("`time_zone` is not a known time zone, got {tz}"))
+    })?;
+    #[cfg(not(feature = "timezones"))]
+    let _ = tz;
+    Ok(())
+}
+
+/// Split the parameters of a dtype name like `"Array[List[Int64], 2]"` on the commas that
+/// aren't nested in brackets.
+fn split_dtype_params(params: &str) -> Vec<&str> {
+    let mut out = vec![];
+    let mut depth = 0usize;
+    let mut start = 0;
+    for (i, c) in params.char_indices() {
+        match c {
+            '[' => depth += 1,
+            ']' => depth = depth.saturating_sub(1),
+            ',' if depth == 0 => {
+                out.push(params[start..i].trim());
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    out.push(params[start..].trim());
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use polars_core::df;
+
+    fn message(err: PyErr) -> String {
+        Python::with_gil(|py| err.value_bound(py).to_string())
+    }
+
+    fn assert_equal(out: &DataFrame, expected: &DataFrame) {
+        assert!(out.equals_missing(expected), "{out} != {expected}");
+    }
+
+    #[test]
+    fn null_count() {
+        let df = df!(
+            "a" => [Some(1), None, Some(3)],
+            "b" => [None::<&str>, None, Some("c")],
+        )
+        .unwrap();
+        let out = PyDataFrame(df).null_count().0;
+        let expected = df!("a" => [1 as IdxSize], "b" => [2 as IdxSize]).unwrap();
+        assert_equal(&out, &expected);
+    }
+
+    #[test]
+    fn explode() {
+        let lists = [
+            Series::new("".into(), [1i64, 2]),
+            Series::new("".into(), [3i64]),
+        ];
+        let strings = [
+            Series::new("".into(), ["x", "y"]),
+            Series::new("".into(), ["z"]),
+        ];
+        let mismatched = [
+            Series::new("".into(), ["x"]),
+            Series::new("".into(), ["y", "z"]),
+        ];
+        let df = DataFrame::new(vec![
+            Column::new("id".into(), [1i64, 2]),
+            Column::new("a".into(), lists),
+            Column::new("b".into(), strings),
+            Column::new("c".into(), mismatched),
+        ])
+        .unwrap();
+        let df = PyDataFrame(df);
+
+        let out = df.explode(vec!["a".into()]).unwrap().0;
+        let expected = df!("id" => [1i64, 1, 2], "a" => [1i64, 2, 3]).unwrap();
+        assert_equal(&out.select(["id", "a"]).unwrap(), &expected);
+
+        let out = df.explode(vec!["a".into(), "b".into()]).unwrap().0;
+        let expected = df!(
+            "id" => [1i64, 1, 2],
+            "a" => [1i64, 2, 3],
+            "b" => ["x", "y", "z"],
+        )
+        .unwrap();
+        assert_equal(&out.select(["id", "a", "b"]).unwrap(), &expected);
+
+        // both columns have 3 elements, but not in the same rows
+        let err = df.explode(vec!["a".into(), "c".into()]).unwrap_err();
+        Python::with_gil(|py| assert!(err.is_instance_of::<crate::error::ShapeError>(py)));
+        assert!(message(err).contains("exploded columns must have matching element counts"));
+
+        let err = df.explode(vec!["id".into()]).unwrap_err();
+        assert!(message(err).contains("cannot explode column 'id' of dtype i64"));
+    }
+
+    #[test]
+    fn group_by_sum_keeps_the_group_order() {
+        let df = df!(
+            "k1" => ["a", "a", "b", "a"],
+            "k2" => [1i64, 1, 1, 2],
+            "v" => [1i64, 2, 3, 4],
+        )
+        .unwrap();
+        let out = PyDataFrame(df)
+            .group_by_sum(&["k1", "k2"], &["v"])
+            .unwrap()
+            .0;
+        let expected = df!(
+            "k1" => ["a", "b", "a"],
+            "k2" => [1i64, 1, 2],
+            "v_sum" => [3i64, 3, 4],
+        )
+        .unwrap();
+        assert_equal(&out, &expected);
+    }
+
+    #[test]
+    fn concat_casts_to_the_supertypes() {
+        let a = df!("x" => [1i32, 2], "y" => ["a", "b"]).unwrap();
+        let b = df!("x" => [0.5f64], "y" => ["c"]).unwrap();
+
+        let out = PyDataFrame::concat(vec![PyDataFrame(a.clone()), PyDataFrame(b)])
+            .unwrap()
+            .0;
+        let expected = df!("x" => [1.0f64, 2.0, 0.5], "y" => ["a", "b", "c"]).unwrap();
+        assert_equal(&out, &expected);
+
+        let out = PyDataFrame::concat(vec![PyDataFrame(a.clone())]).unwrap().0;
+        assert_equal(&out, &a);
+        assert_eq!(PyDataFrame::concat(vec![]).unwrap().0.shape(), (0, 0));
+    }
+
+    #[test]
+    fn concat_rejects_frames_that_dont_align() {
+        let a = df!("x" => [1i32, 2], "y" => ["a", "b"]).unwrap();
+        let reordered = a.select(["y", "x"]).unwrap();
+        let binary = df!("x" => [b"z".as_slice()], "y" => ["c"]).unwrap();
+        for other in [reordered, binary] {
+            let err =
+                PyDataFrame::concat(vec![PyDataFrame(a.clone()), PyDataFrame(other)]).unwrap_err();
+            Python::with_gil(|py| assert!(err.is_instance_of::<crate::error::SchemaError>(py)));
+        }
+    }
+
+    #[test]
+    fn assert_frame_equal_reports_the_first_difference() {
+        let left = df!("a" => [1i64, 2, 3], "b" => [Some("x"), None, Some("z")]).unwrap();
+        let left = PyDataFrame(left);
+        let renamed = {
+            let mut df = left.0.clone();
+            df.rename("a", "c".into()).unwrap();
+            PyDataFrame(df)
+        };
+        let cast = {
+            let mut df = left.0.clone();
+            df.apply("a", |c| c.cast(&DataType::Int32).unwrap())
+                .unwrap();
+            PyDataFrame(df)
+        };
+        let filled = {
+            let mut df = left.0.clone();
+            df.with_column(Column::new("b".into(), ["x", "y", "z"]))
+                .unwrap();
+            PyDataFrame(df)
+        };
+
+        left.assert_frame_equal(&left.clone(), true, true).unwrap();
+        left.assert_frame_equal(&renamed, true, false).unwrap();
+        left.assert_frame_equal(&cast, false, true).unwrap();
+
+        let cases = [
+            (filled, "frames differ at row 1, column 'b': null != \"y\""),
+            (
+                PyDataFrame(left.0.head(Some(2))),
+                "frames have a different shape: (3, 2) != (2, 2)",
+            ),
+            (renamed, "frames have different column names"),
+            (cast, "column 'a' has a different dtype: i64 != i32"),
+        ];
+        for (right, expected) in cases {
+            let err = left.assert_frame_equal(&right, true, true).unwrap_err();
+            Python::with_gil(|py| assert!(err.is_instance_of::<PyAssertionError>(py)));
+            let msg = message(err);
+            assert!(msg.contains(expected), "{msg}");
+        }
+    }
+
+    #[cfg(feature = "ipc")]
+    #[test]
+    fn read_ipc() {
+        use polars::prelude::{IpcWriter, SerWriter};
+
+        let mut df = df!("a" => [1i64, 2], "b" => ["x", "y"]).unwrap();
+        let path = std::env::temp_dir().join(format!("pyo3-polars-{}.ipc", std::process::id()));
+        IpcWriter::new(std::fs::File::create(&path).unwrap())
+            .finish(&mut df)
+            .unwrap();
+        let path = path.to_str().unwrap();
+
+        Python::with_gil(|py| {
+            let out = PyDataFrame::read_ipc(py, path, Some(vec!["b".into()])).unwrap();
+            assert_equal(&out.0, &df.select(["b"]).unwrap());
+
+            let err = PyDataFrame::read_ipc(py, "does-not-exist.ipc", None).unwrap_err();
+            assert!(err.is_instance_of::<pyo3::exceptions::PyOSError>(py));
+        });
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[cfg(feature = "lazy")]
+    #[test]
+    fn profile_timings() {
+        let df = df!("a" => [1i64, 2, 3]).unwrap();
+        let lf = PyLazyFrame(df.lazy().filter(col("a").gt(polars_plan::dsl::lit(1i64))));
+        let (out, timings) = Python::with_gil(|py| lf.profile(py)).unwrap();
+        assert_equal(&out.0, &df!("a" => [2i64, 3]).unwrap());
+
+        let timings = timings.0;
+        assert_eq!(timings.get_column_names(), ["node", "start", "end"]);
+        assert_eq!(
+            timings.dtypes(),
+            [DataType::String, DataType::UInt64, DataType::UInt64]
+        );
+        assert!(timings.height() > 0);
+    }
+
+    #[cfg(feature = "lazy")]
+    #[test]
+    fn plan_errors_are_deserialization_errors() {
+        // A map `{"NotAPlan": 1}` is well-formed in both formats, but not a plan of any version.
+        let cases = [
+            (
+                SerializeFormat::Binary,
+                b"\xa1\x68NotAPlan\x01".as_slice(),
+                true,
+            ),
+            (SerializeFormat::Binary, b"\xa1".as_slice(), false),
+            (
+                SerializeFormat::Json,
+                br#"{"NotAPlan": 1}"#.as_slice(),
+                true,
+            ),
+            (SerializeFormat::Json, br#"{"NotAPlan""#.as_slice(), false),
+        ];
+        for (format, bytes, other_version) in cases {
+            let err = PyLazyFrame::deserialize(bytes, format).unwrap_err();
+            Python::with_gil(|py| {
+                assert!(err.is_instance_of::<crate::error::DeserializationError>(py))
+            });
+            let msg = message(err);
+            assert!(
+                msg.starts_with("could not deserialize the LazyFrame"),
+                "{msg}"
+            );
+            assert_eq!(
+                msg.contains("built against rust polars"),
+                other_version,
+                "{msg}"
+            );
+        }
+    }
+
+    #[test]
+    fn unknown_dtype_name() {
+        let err = dtype_from_class_name("Int33").unwrap_err();
+        assert!(message(err).contains("'Int33' is not a Polars data type"));
+    }
+
+    #[test]
+    fn split_nested_dtype_params() {
+        assert_eq!(
+            split_dtype_params("List[Array[Float64, 2]], Int8"),
+            ["List[Array[Float64, 2]]", "Int8"]
+        );
+        assert_eq!(split_dtype_params("ms, UTC"), ["ms", "UTC"]);
+    }
+
+    #[cfg(feature = "dtype-decimal")]
+    #[test]
+    fn decimal_dtype_bounds() {
+        assert_eq!(
+            decimal_dtype(Some(38), 38).unwrap(),
+            DataType::Decimal(Some(38), Some(38))
+        );
+        assert_eq!(
+            decimal_dtype(None, 2).unwrap(),
+            DataType::Decimal(None, Some(2))
+        );
+        for (precision, scale, expected) in [
+            (Some(39), 2, "precision must be between 1 and 38"),
+            (Some(0), 0, "precision must be between 1 and 38"),
+            (
+                Some(10),
+                12,
+                "scale must be between 0 and the precision (10)",
+            ),
+            (
+                Some(10),
+                -1,
+                "scale must be between 0 and the precision (10)",
+            ),
+            (None, 39, "scale must be between 0 and the precision (38)"),
+        ] {
+            let msg = message(decimal_dtype(precision, scale).unwrap_err());
+            assert!(msg.contains(expected), "{msg}");
+        }
+    }
+}
@@ -0,0 +1,61 @@
+"""Time the conversions of a `PyDataFrame` from and to python polars, for frames of different widths.
+
+A frame is extracted through a single arrow stream (`__arrow_c_stream__`). Objects without
+it fall back to converting every series with a python call.
+
+A frame is converted to python as a python series per column that python polars collects into
+a frame, or, for frames with more columns than `WIDE_FRAME_COLUMNS`, through a single arrow
+stream. The `into_py` column is the conversion as it's done, the other columns time each path
+for every width.
+
+Run with `make bench`, which builds the extension in release mode.
+"""
+
+import timeit
+
+import polars as pl
+
+import pyo3_polars_tests as t
+
+
+class ColumnsOnly:
+    """Only exposes the columns, so the frame is converted series by series."""
+
+    def __init__(self, df):
+        self.width = df.width
+        self.get_columns = df.get_columns
+
+
+def best_of(f, number=10, repeat=5):
+    return min(timeit.repeat(f, number=number, repeat=repeat)) / number
+
+
+def ms(seconds):
+    return f"{seconds * 1e3:>10.3f}ms"
+
+
+print(f"polars {pl.__version__}")
+print()
+print("extract, 1000 rows")
+print(f"{'columns':>8} {'stream':>12} {'per series':>12} {'speedup':>8}")
+for width in [1, 10, 100, 1_000, 10_000]:
+    df = pl.DataFrame({f"c{i}": range(1_000) for i in range(width)})
+    columns = ColumnsOnly(df)
+    assert t.frame_shape(df) == t.frame_shape(columns) == df.shape
+
+    stream = best_of(lambda: t.frame_shape(df))
+    per_series = best_of(lambda: t.frame_shape(columns))
+    print(f"{width:>8} {ms(stream)} {ms(per_series)} {per_series / stream:>7.1f}x")
+
+print()
+print("into_py, 100 rows")
+print(f"{'columns':>8} {'into_py':>12} {'stream':>12} {'per series':>12}")
+for width in [10, 100, 500, 1_000, 2_000, 5_000, 10_000, 50_000]:
+    assert t.make_frame(width, 100).shape == (100, width)
+    # The wide frames are slow to convert series by series, so they're timed fewer times.
+    number, repeat = (10, 5) if width <= 10_000 else (1, 3)
+
+    into_py = best_of(lambda: t.make_frame(width, 100), number, repeat)
+    stream = best_of(lambda: pl.DataFrame(t.make_frame_stream(width, 100)), number, repeat)
+    per_series = best_of(lambda: pl.DataFrame(t.make_frame_columns(width, 100)), number, repeat)
+    print(f"{width:>8} {ms(into_py)} {ms(stream)} {ms(per_series)}")
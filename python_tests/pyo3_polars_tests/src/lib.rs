@@ -0,0 +1,461 @@
+//! A python extension that exposes the conversions of `pyo3-polars` to the integration tests in
+//! `../tests`, which check them against python polars and pyarrow.
+
+use polars::prelude::*;
+use polars_lazy::frame::ScanArgsAnonymous;
+use polars_lazy::prelude::{AnonymousScan, AnonymousScanArgs};
+use pyo3::prelude::*;
+use pyo3::types::{PyCapsule, PyDict};
+use pyo3_polars::error::PyPolarsErr;
+use pyo3_polars::{
+    compat_level, enum_dtype, CompatLevel, PolarsAllocator, PyAnyValue, PyDataFrame,
+    PyDataFrameColumns, PyDataFrameIter, PyDataType, PyExpr, PyField, PyLazyFrame, PySchema,
+    PySeries, PySeriesList,
+};
+
+#[global_allocator]
+static ALLOC: PolarsAllocator = PolarsAllocator::new();
+
+#[pyfunction]
+fn frame_roundtrip(pydf: PyDataFrame) -> PyDataFrame {
+    pydf
+}
+
+#[pyfunction]
+fn lazy_roundtrip(lf: PyLazyFrame) -> PyLazyFrame {
+    lf
+}
+
+/// Pack two columns into a struct column named `pair`.
+#[pyfunction]
+fn to_struct(pydf: PyDataFrame, col_a: &str, col_b: &str) -> PyResult<PySeries> {
+    let df: DataFrame = pydf.into();
+    let out = df
+        .select([col_a, col_b])
+        .map_err(PyPolarsErr::from)?
+        .into_struct("pair".into());
+    Ok(PySeries(out.into_series()))
+}
+
+/// Drop the rows with nulls and report how many rows were dropped next to the frame.
+#[pyfunction]
+fn drop_nulls_with_meta(py: Python<'_>, pydf: PyDataFrame) -> PyResult<(PyDataFrame, PyObject)> {
+    let df = pydf
+        .0
+        .drop_nulls::<String>(None)
+        .map_err(PyPolarsErr::from)?;
+    let meta = PyDict::new_bound(py);
+    meta.set_item("n_dropped", pydf.0.height() - df.height())?;
+    Ok((PyDataFrame(df), meta.into_py(py)))
+}
+
+/// Return the series as a pyarrow array.
+#[pyfunction]
+fn to_pyarrow(py: Python<'_>, pyseries: PySeries) -> PyResult<PyObject> {
+    pyseries.into_pyarrow(py)
+}
+
+#[pyfunction]
+fn schema_roundtrip(schema: PySchema) -> PySchema {
+    schema
+}
+
+/// Find the name of the first column that contains `value`.
+///
+/// Columns after the match are never converted.
+#[pyfunction]
+fn find_column(columns: PyDataFrameColumns<'_>, value: i64) -> PyResult<Option<String>> {
+    for s in columns {
+        let s = s?.0;
+        let found = s.equal(value).map_err(PyPolarsErr::from)?.any();
+        if found {
+            return Ok(Some(s.name().to_string()));
+        }
+    }
+    Ok(None)
+}
+
+/// Apply a filter and a projection to the plan, without collecting it.
+#[pyfunction]
+fn lazy_filter_select(lf: PyLazyFrame, predicate: PyExpr, exprs: Vec<PyExpr>) -> PyLazyFrame {
+    lf.filter(predicate).select(exprs)
+}
+
+/// The columns an expression reads and its output dtype on `schema`.
+#[pyfunction]
+fn inspect_expr(expr: PyExpr, schema: PySchema) -> PyResult<(Vec<String>, PyDataType)> {
+    Ok((expr.root_names(), expr.output_dtype(&schema)?))
+}
+
+/// Return the dtype of a series.
+#[pyfunction]
+fn series_dtype(pyseries: PySeries) -> PyDataType {
+    PyDataType(pyseries.0.dtype().clone())
+}
+
+#[pyfunction]
+fn series_roundtrip(pyseries: PySeries) -> PySeries {
+    pyseries
+}
+
+/// Export a series with the arrow types every arrow consumer understands.
+#[pyfunction]
+fn series_oldest_compat(py: Python<'_>, pyseries: PySeries) -> PyResult<PyObject> {
+    pyseries.try_into_py_with_compat_level(py, CompatLevel::oldest())
+}
+
+/// Export a series with the given compat level, newer levels than polars knows are clamped.
+#[pyfunction]
+fn series_compat_level(py: Python<'_>, pyseries: PySeries, level: u16) -> PyResult<PyObject> {
+    pyseries.try_into_py_with_compat_level(py, compat_level(level))
+}
+
+/// Import a series with `NaN` values as nulls.
+#[pyfunction]
+fn nan_to_null(
+    #[pyo3(from_py_with = "PySeries::extract_nan_to_null")] pyseries: PySeries,
+) -> PySeries {
+    pyseries
+}
+
+#[pyfunction]
+fn scalar_roundtrip(value: PyAnyValue<'static>) -> PyAnyValue<'static> {
+    value
+}
+
+/// Cast a string series to an enum with `categories`.
+#[pyfunction]
+fn to_enum(pyseries: PySeries, categories: Vec<String>) -> PyResult<PySeries> {
+    let categories = categories.iter().map(String::as_str).collect::<Vec<_>>();
+    let s = pyseries
+        .0
+        .strict_cast(&enum_dtype(&categories).map_err(PyPolarsErr::from)?)
+        .map_err(PyPolarsErr::from)?;
+    Ok(PySeries(s))
+}
+
+/// Return the given columns in reverse order.
+#[pyfunction]
+fn reverse_columns(columns: PySeriesList) -> PySeriesList {
+    PySeriesList(columns.0.into_iter().rev().collect())
+}
+
+/// Build a categorical series from physical `codes`, in the categorical space of `pyseries`.
+#[pyfunction]
+fn with_codes(pyseries: PySeries, codes: Vec<u32>) -> PyResult<PySeries> {
+    let Some(rev_map) = pyseries.categorical_rev_map() else {
+        return Err(PyPolarsErr::from(
+            polars_err!(InvalidOperation: "expected a categorical series"),
+        )
+        .into());
+    };
+    if let Some(code) = codes
+        .iter()
+        .find(|&&code| rev_map.get_optional(code).is_none())
+    {
+        return Err(
+            PyPolarsErr::from(polars_err!(OutOfBounds: "unknown category code {}", code)).into(),
+        );
+    }
+    let (is_enum, ordering) = match pyseries.dtype() {
+        DataType::Enum(_, ordering) => (true, *ordering),
+        DataType::Categorical(_, ordering) => (false, *ordering),
+        _ => unreachable!(),
+    };
+    let cats = UInt32Chunked::from_vec(pyseries.name().clone(), codes);
+    // SAFETY: all codes were checked to be in the rev-map.
+    let ca = unsafe {
+        CategoricalChunked::from_cats_and_rev_map_unchecked(cats, rev_map, is_enum, ordering)
+    };
+    Ok(PySeries(ca.into_series()))
+}
+
+/// Prefix the name of a field and make its dtype a list of the original dtype.
+#[pyfunction]
+fn to_list_field(py: Python<'_>, field: PyField) -> PyResult<PyObject> {
+    let name = format!("list_{}", field.0.name());
+    PyField(Field::new(
+        name.into(),
+        DataType::List(Box::new(field.0.dtype)),
+    ))
+    .try_into_py(py)
+}
+
+/// Select `columns` from batches of `size` rows, one batch at a time.
+#[pyfunction]
+fn select_batches(pydf: PyDataFrame, size: usize, columns: Vec<String>) -> PyDataFrameIter {
+    let df = pydf.0;
+    let offsets = (0..df.height()).step_by(size.max(1));
+    PyDataFrameIter::new(offsets.map(move |offset| {
+        df.slice(offset as i64, size)
+            .select(columns.iter().map(String::as_str))
+    }))
+}
+
+#[pyfunction]
+fn to_numpy_structured(py: Python<'_>, pydf: PyDataFrame) -> PyResult<PyObject> {
+    pydf.to_numpy_structured(py)
+}
+
+#[pyfunction]
+fn to_binary_offset(pyseries: PySeries) -> PyResult<PySeries> {
+    let s = pyseries
+        .0
+        .cast(&DataType::BinaryOffset)
+        .map_err(PyPolarsErr::from)?;
+    Ok(PySeries(s))
+}
+
+/// Import any object that implements `__arrow_c_stream__`, like a pyarrow table.
+#[pyfunction]
+fn from_arrow_stream(ob: &Bound<'_, PyAny>) -> PyResult<PyDataFrame> {
+    PyDataFrame::from_arrow_c_stream(ob)
+}
+
+/// Import a pyarrow table or reader batch by batch, through its `to_batches()` or iteration.
+#[pyfunction]
+fn from_record_batches(ob: &Bound<'_, PyAny>) -> PyResult<PyDataFrame> {
+    PyDataFrame::from_arrow_record_batches(ob)
+}
+
+#[pyfunction]
+fn lazy_from_arrow(ob: &Bound<'_, PyAny>) -> PyResult<PyLazyFrame> {
+    PyLazyFrame::from_arrow_backed(ob)
+}
+
+/// A rust data source of the numbers `0..n` and their squares.
+struct RangeScan {
+    n: i64,
+}
+
+impl AnonymousScan for RangeScan {
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn schema(&self, _infer_schema_length: Option<usize>) -> PolarsResult<SchemaRef> {
+        let schema = Schema::from_iter([
+            Field::new("i".into(), DataType::Int64),
+            Field::new("square".into(), DataType::Int64),
+        ]);
+        Ok(Arc::new(schema))
+    }
+
+    fn scan(&self, args: AnonymousScanArgs) -> PolarsResult<DataFrame> {
+        let n = args
+            .n_rows
+            .map_or(self.n, |n_rows| self.n.min(n_rows as i64));
+        let i = Series::new("i".into(), (0..n).collect::<Vec<_>>());
+        let square = Series::new("square".into(), (0..n).map(|i| i * i).collect::<Vec<_>>());
+        let df = DataFrame::new(vec![i.into_column(), square.into_column()])?;
+        match args.with_columns {
+            Some(columns) => df.select(columns.iter().cloned()),
+            None => Ok(df),
+        }
+    }
+
+    fn allows_projection_pushdown(&self) -> bool {
+        true
+    }
+
+    fn allows_slice_pushdown(&self) -> bool {
+        true
+    }
+}
+
+#[pyfunction]
+fn scan_range(n: i64) -> PyResult<PyLazyFrame> {
+    let args = ScanArgsAnonymous {
+        name: "RANGE",
+        ..Default::default()
+    };
+    PyLazyFrame::anonymous_scan(Arc::new(RangeScan { n }), args)
+}
+
+#[pyfunction]
+fn profile(py: Python<'_>, lf: PyLazyFrame) -> PyResult<(PyDataFrame, PyDataFrame)> {
+    lf.profile(py)
+}
+
+fn sorted_flag_name(sorted: IsSorted) -> &'static str {
+    match sorted {
+        IsSorted::Ascending => "ascending",
+        IsSorted::Descending => "descending",
+        IsSorted::Not => "not",
+    }
+}
+
+#[pyfunction]
+fn sorted_flag(pyseries: PySeries) -> &'static str {
+    sorted_flag_name(pyseries.0.is_sorted_flag())
+}
+
+/// The sorted flags of the columns of a frame, as they arrive in rust.
+#[pyfunction]
+fn column_sorted_flags(pydf: PyDataFrame) -> Vec<&'static str> {
+    let columns = pydf.0.get_columns();
+    columns
+        .iter()
+        .map(|c| sorted_flag_name(c.is_sorted_flag()))
+        .collect()
+}
+
+/// Only extract a frame, to time the conversion from python without the conversion back.
+#[pyfunction]
+fn frame_shape(pydf: PyDataFrame) -> (usize, usize) {
+    pydf.0.shape()
+}
+
+/// A frame of `width` integer columns with `height` rows.
+fn int_frame(width: usize, height: usize) -> PyResult<DataFrame> {
+    let columns = (0..width)
+        .map(|i| {
+            Column::new(
+                format!("c{i}").into(),
+                (0..height as i64).collect::<Vec<_>>(),
+            )
+        })
+        .collect();
+    Ok(DataFrame::new(columns).map_err(PyPolarsErr::from)?)
+}
+
+/// Build a frame in rust, to time the conversion to python.
+#[pyfunction]
+fn make_frame(width: usize, height: usize) -> PyResult<PyDataFrame> {
+    Ok(PyDataFrame(int_frame(width, height)?))
+}
+
+/// The columns of `make_frame` as python series, to time building a frame from them.
+#[pyfunction]
+fn make_frame_columns(width: usize, height: usize) -> PyResult<PySeriesList> {
+    let df = int_frame(width, height)?;
+    let columns = df.take_columns().into_iter();
+    Ok(PySeriesList(
+        columns.map(Column::take_materialized_series).collect(),
+    ))
+}
+
+/// `make_frame` as an arrow stream, to time building a frame from it.
+#[pyfunction]
+fn make_frame_stream(width: usize, height: usize) -> PyResult<ArrowFrame> {
+    Ok(ArrowFrame(PyDataFrame(int_frame(width, height)?)))
+}
+
+/// Raise a polars error of the given kind, to check the python exception it's raised as.
+#[pyfunction]
+fn raise_polars_error(kind: &str) -> PyResult<()> {
+    let msg = || "boom".into();
+    let err = match kind {
+        "compute" => PolarsError::ComputeError(msg()),
+        "no_data" => PolarsError::NoData(msg()),
+        "shape" => PolarsError::ShapeMismatch(msg()),
+        "schema" => PolarsError::SchemaMismatch(msg()),
+        "io" => std::io::Error::other("boom").into(),
+        "out_of_bounds" => PolarsError::OutOfBounds(msg()),
+        "invalid_operation" => PolarsError::InvalidOperation(msg()),
+        "duplicate" => PolarsError::Duplicate(msg()),
+        "column_not_found" => PolarsError::ColumnNotFound(msg()),
+        "schema_field_not_found" => PolarsError::SchemaFieldNotFound(msg()),
+        "struct_field_not_found" => PolarsError::StructFieldNotFound(msg()),
+        "string_cache_mismatch" => PolarsError::StringCacheMismatch(msg()),
+        "sql_interface" => PolarsError::SQLInterface(msg()),
+        "sql_syntax" => PolarsError::SQLSyntax(msg()),
+        "context" => PolarsError::ComputeError(msg()).context("while testing".into()),
+        _ => unreachable!(),
+    };
+    Err(PyPolarsErr::from(err).into())
+}
+
+/// Call `f`, an exception it raises is the cause of the error of this function.
+#[pyfunction]
+fn call_python(f: &Bound<'_, PyAny>) -> PyResult<PyObject> {
+    let out = f.call0().map_err(PyPolarsErr::from_py_err)?;
+    Ok(out.unbind())
+}
+
+/// A frame that arrow consumers can read through `__arrow_c_stream__`.
+#[pyclass]
+struct ArrowFrame(PyDataFrame);
+
+#[pymethods]
+impl ArrowFrame {
+    #[pyo3(signature = (requested_schema=None))]
+    fn __arrow_c_stream__<'py>(
+        &self,
+        py: Python<'py>,
+        requested_schema: Option<Bound<'py, PyAny>>,
+    ) -> PyResult<Bound<'py, PyCapsule>> {
+        self.0.__arrow_c_stream__(py, requested_schema)
+    }
+}
+
+#[pyfunction]
+fn to_arrow_frame(pydf: PyDataFrame) -> ArrowFrame {
+    ArrowFrame(pydf)
+}
+
+/// A series that arrow consumers can read through `__arrow_c_array__`.
+#[pyclass]
+struct ArrowSeries(PySeries);
+
+#[pymethods]
+impl ArrowSeries {
+    #[pyo3(signature = (requested_schema=None))]
+    fn __arrow_c_array__<'py>(
+        &self,
+        py: Python<'py>,
+        requested_schema: Option<Bound<'py, PyAny>>,
+    ) -> PyResult<(Bound<'py, PyCapsule>, Bound<'py, PyCapsule>)> {
+        self.0.__arrow_c_array__(py, requested_schema)
+    }
+}
+
+#[pyfunction]
+fn to_arrow_series(pyseries: PySeries) -> ArrowSeries {
+    ArrowSeries(pyseries)
+}
+
+#[pymodule]
+fn pyo3_polars_tests(_py: Python, m: &Bound<PyModule>) -> PyResult<()> {
+    pyo3_polars::register_exceptions(m)?;
+    m.add_function(wrap_pyfunction!(frame_roundtrip, m)?)?;
+    m.add_function(wrap_pyfunction!(lazy_roundtrip, m)?)?;
+    m.add_function(wrap_pyfunction!(to_struct, m)?)?;
+    m.add_function(wrap_pyfunction!(drop_nulls_with_meta, m)?)?;
+    m.add_function(wrap_pyfunction!(to_pyarrow, m)?)?;
+    m.add_function(wrap_pyfunction!(schema_roundtrip, m)?)?;
+    m.add_function(wrap_pyfunction!(find_column, m)?)?;
+    m.add_function(wrap_pyfunction!(lazy_filter_select, m)?)?;
+    m.add_function(wrap_pyfunction!(inspect_expr, m)?)?;
+    m.add_function(wrap_pyfunction!(series_dtype, m)?)?;
+    m.add_function(wrap_pyfunction!(series_roundtrip, m)?)?;
+    m.add_function(wrap_pyfunction!(series_oldest_compat, m)?)?;
+    m.add_function(wrap_pyfunction!(series_compat_level, m)?)?;
+    m.add_function(wrap_pyfunction!(to_binary_offset, m)?)?;
+    m.add_function(wrap_pyfunction!(nan_to_null, m)?)?;
+    m.add_function(wrap_pyfunction!(scalar_roundtrip, m)?)?;
+    m.add_function(wrap_pyfunction!(to_enum, m)?)?;
+    m.add_function(wrap_pyfunction!(reverse_columns, m)?)?;
+    m.add_function(wrap_pyfunction!(with_codes, m)?)?;
+    m.add_function(wrap_pyfunction!(to_list_field, m)?)?;
+    m.add_function(wrap_pyfunction!(select_batches, m)?)?;
+    m.add_function(wrap_pyfunction!(to_numpy_structured, m)?)?;
+    m.add_function(wrap_pyfunction!(from_arrow_stream, m)?)?;
+    m.add_function(wrap_pyfunction!(from_record_batches, m)?)?;
+    m.add_function(wrap_pyfunction!(lazy_from_arrow, m)?)?;
+    m.add_function(wrap_pyfunction!(scan_range, m)?)?;
+    m.add_function(wrap_pyfunction!(profile, m)?)?;
+    m.add_function(wrap_pyfunction!(sorted_flag, m)?)?;
+    m.add_function(wrap_pyfunction!(column_sorted_flags, m)?)?;
+    m.add_function(wrap_pyfunction!(frame_shape, m)?)?;
+    m.add_function(wrap_pyfunction!(make_frame, m)?)?;
+    m.add_function(wrap_pyfunction!(make_frame_columns, m)?)?;
+    m.add_function(wrap_pyfunction!(make_frame_stream, m)?)?;
+    m.add_function(wrap_pyfunction!(raise_polars_error, m)?)?;
+    m.add_function(wrap_pyfunction!(call_python, m)?)?;
+    m.add_class::<ArrowFrame>()?;
+    m.add_function(wrap_pyfunction!(to_arrow_frame, m)?)?;
+    m.add_class::<ArrowSeries>()?;
+    m.add_function(wrap_pyfunction!(to_arrow_series, m)?)?;
+    Ok(())
+}
@@ -0,0 +1,76 @@
+import polars as pl
+import pytest
+
+import pyo3_polars_tests as t
+
+
+def test_schema_field_order():
+    # the field order of a schema survives the round-trip
+    schema = {"z": pl.Int64, "a": pl.String, "m": pl.List(pl.Float32)}
+    out = t.schema_roundtrip(schema)
+    assert list(out.keys()) == ["z", "a", "m"]
+    assert out == schema
+    assert list(t.schema_roundtrip(pl.Schema(schema)).keys()) == ["z", "a", "m"]
+    fields = [pl.Field(name, dtype) for name, dtype in schema.items()]
+    assert list(t.schema_roundtrip(fields).keys()) == ["z", "a", "m"]
+    assert t.schema_roundtrip({"a": pl.Int128}) == {"a": pl.Int128}
+
+
+def test_schema_duplicate_fields():
+    with pytest.raises(t.DuplicateError, match="more than once"):
+        t.schema_roundtrip([pl.Field("a", pl.Int64), pl.Field("a", pl.String)])
+
+
+@pytest.mark.parametrize(
+    "dtype",
+    [
+        pl.Int8,
+        pl.Datetime("ms", "UTC"),
+        pl.List(pl.Struct({"a": pl.String, "b": pl.List(pl.Int64)})),
+        pl.Struct({"x": pl.Datetime("ns")}),
+    ],
+)
+def test_series_dtype(dtype):
+    # dtypes can be returned on their own, including parametric ones
+    assert t.series_dtype(pl.Series([], dtype=dtype)) == dtype
+
+
+def test_time_zones():
+    # unknown time zones are rejected when the dtype is converted
+    schema = {"a": pl.Datetime("ms", "Europe/Amsterdam")}
+    assert t.schema_roundtrip(schema) == schema
+    with pytest.raises(ValueError, match="Europe/Atlantis"):
+        t.schema_roundtrip({"a": pl.Datetime("ms", "Europe/Atlantis")})
+
+
+def test_dtype_names():
+    named = {
+        "a": "Int32",
+        "b": "Datetime[ms, UTC]",
+        "c": "Duration[ns]",
+        "d": "List[Array[Float64, 2]]",
+        "e": "Decimal[10, 2]",
+        "f": "Datetime",
+        "g": "Decimal[38, 38]",
+    }
+    assert t.schema_roundtrip(named) == {
+        "a": pl.Int32,
+        "b": pl.Datetime("ms", "UTC"),
+        "c": pl.Duration("ns"),
+        "d": pl.List(pl.Array(pl.Float64, 2)),
+        "e": pl.Decimal(10, 2),
+        "f": pl.Datetime("us"),
+        "g": pl.Decimal(38, 38),
+    }
+    with pytest.raises(TypeError, match="'Int33' is not a Polars data type"):
+        t.schema_roundtrip({"a": "Int33"})
+    # the bounds themselves are covered by the unit tests of `decimal_dtype`
+    with pytest.raises(ValueError, match="precision must be between 1 and 38"):
+        t.schema_roundtrip({"a": "Decimal[39, 2]"})
+
+
+def test_field():
+    # fields convert back to polars fields
+    out = t.to_list_field(pl.Field("a", pl.Datetime("ms")))
+    assert isinstance(out, pl.Field)
+    assert out == pl.Field("list_a", pl.List(pl.Datetime("ms")))
@@ -0,0 +1,44 @@
+import pytest
+
+import pyo3_polars_tests as t
+
+
+@pytest.mark.parametrize(
+    ("kind", "exception"),
+    [
+        ("compute", t.ComputeError),
+        ("no_data", t.NoDataError),
+        ("shape", t.ShapeError),
+        ("schema", t.SchemaError),
+        ("io", IOError),
+        ("out_of_bounds", IndexError),
+        ("invalid_operation", ValueError),
+        ("duplicate", t.DuplicateError),
+        ("column_not_found", t.ColumnNotFound),
+        ("schema_field_not_found", t.SchemaFieldNotFound),
+        ("struct_field_not_found", t.StructFieldNotFound),
+        ("string_cache_mismatch", t.StringCacheMismatchError),
+        ("sql_interface", t.SQLInterface),
+        ("sql_syntax", t.SQLSyntax),
+    ],
+)
+def test_polars_error_kinds(kind, exception):
+    # every polars error is raised as the exception of its kind
+    with pytest.raises(exception, match="boom"):
+        t.raise_polars_error(kind)
+
+
+def test_error_context():
+    with pytest.raises(t.ComputeError, match="while testing") as e:
+        t.raise_polars_error("context")
+    assert "boom" in str(e.value)
+
+
+def test_python_callback_error_is_the_cause():
+    def callback():
+        raise KeyError("missing")
+
+    assert t.call_python(lambda: 1) == 1
+    with pytest.raises(t.ComputeError, match="missing") as e:
+        t.call_python(callback)
+    assert isinstance(e.value.__cause__, KeyError)
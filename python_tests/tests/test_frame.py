@@ -0,0 +1,208 @@
+from datetime import datetime
+
+import numpy as np
+import polars as pl
+import pyarrow as pa
+import pytest
+from polars.testing import assert_frame_equal
+
+import pyo3_polars_tests as t
+
+
+@pytest.mark.parametrize("name", ["", "a", " padded ", "ünïcödé", "with.dot", "0"])
+def test_column_names(name):
+    assert t.frame_roundtrip(pl.DataFrame({name: [1]})).columns == [name]
+
+
+def test_chunked_frame():
+    # frames are imported through a single arrow stream, also if their columns are chunked
+    a = pl.DataFrame({"a": [1, 2], "b": ["x", "y"], "c": [{"f": 1}, {"f": 2}]})
+    chunked = pl.concat([a, a], rechunk=False)
+    assert chunked.n_chunks() == 2
+    assert_frame_equal(t.frame_roundtrip(chunked), chunked)
+    assert_frame_equal(t.frame_roundtrip(a.clear()), a.clear())
+
+
+def test_empty_frame_keeps_dtypes():
+    schema = pl.Schema({
+        "i8": pl.Int8, "i16": pl.Int16, "i32": pl.Int32, "i64": pl.Int64, "i128": pl.Int128,
+        "u8": pl.UInt8, "u16": pl.UInt16, "u32": pl.UInt32, "u64": pl.UInt64,
+        "f32": pl.Float32, "f64": pl.Float64, "bool": pl.Boolean, "str": pl.String,
+        "bin": pl.Binary, "date": pl.Date, "time": pl.Time,
+        "datetime": pl.Datetime("ms", "Europe/Amsterdam"), "duration": pl.Duration("ns"),
+        "decimal": pl.Decimal(10, 2), "cat": pl.Categorical, "enum": pl.Enum(["x", "y"]),
+        "list": pl.List(pl.Int64), "array": pl.Array(pl.Float64, 2),
+        "struct": pl.Struct({"a": pl.Int64, "b": pl.List(pl.String)}), "null": pl.Null,
+    })
+    out = t.frame_roundtrip(pl.DataFrame(schema=schema))
+    assert out.shape == (0, len(schema))
+    assert out.schema == schema
+
+
+def test_wide_frame():
+    # very wide frames are converted in both directions, python builds them from one stream
+    wide = pl.DataFrame({f"c{i}": [i, None] for i in range(50_000)})
+    out = t.frame_roundtrip(wide)
+    assert out.width == 50_000
+    assert out.columns[-1] == "c49999"
+    assert out.row(0) == tuple(range(50_000))
+
+    # the sorted flags are set again after the stream is imported
+    wide = wide.with_columns(pl.col("c0").set_sorted(), pl.col("c1").set_sorted(descending=True))
+    out = t.frame_roundtrip(wide)
+    assert out["c0"].flags["SORTED_ASC"]
+    assert out["c1"].flags["SORTED_DESC"]
+    assert not any(out["c2"].flags.values())
+
+    # categoricals are passed series by series, which keeps their categories
+    wide = wide.with_columns(pl.col("c2").cast(pl.String).cast(pl.Categorical))
+    out = t.frame_roundtrip(wide)
+    assert out.schema["c2"] == pl.Categorical
+    assert out["c2"].to_list() == ["2", None]
+
+
+def test_malformed_duck_typed_frame():
+    # duck-typed frames are validated, columns of different lengths raise instead of crashing
+    class MalformedFrame:
+        width = 2
+
+        def get_columns(self):
+            return [pl.Series("a", [1, 2, 3]), pl.Series("b", [1])]
+
+    with pytest.raises(Exception, match="length|height"):
+        t.frame_roundtrip(MalformedFrame())
+
+
+class DuplicateFrame:
+    width = 2
+
+    def get_columns(self):
+        return [pl.Series("a", [1]), pl.Series("a", [2])]
+
+
+@pytest.mark.parametrize(
+    "frame", [DuplicateFrame(), pa.table([[1], [2]], names=["a", "a"])], ids=["duck", "arrow"]
+)
+def test_duplicate_column_names(frame):
+    # duplicate column names are rejected, whichever way the frame is imported
+    with pytest.raises(t.DuplicateError, match="'a'"):
+        t.frame_roundtrip(frame)
+
+
+def test_to_struct():
+    out = t.to_struct(pl.DataFrame({"a": [1, 2], "b": ["x", "y"]}), "a", "b")
+    assert out.name == "pair"
+    assert out.struct.fields == ["a", "b"]
+    assert out.struct.field("b").to_list() == ["x", "y"]
+
+
+def test_frame_and_python_object():
+    df = pl.DataFrame({"a": [1, None, 3], "b": ["x", "y", None]})
+    out, meta = t.drop_nulls_with_meta(df)
+    assert out.rows() == [(1, "x")]
+    assert meta == {"n_dropped": 2}
+
+
+def test_columns_are_converted_lazily():
+    df = pl.DataFrame({"a": [1, 2], "b": [3, 4], "c": [4, 5]})
+    assert t.find_column(df, 4) == "b"
+    assert t.find_column(df, 6) is None
+
+
+def test_from_arrow_stream():
+    # any object with `__arrow_c_stream__` can be imported as a frame
+    table = pa.table({"a": [1, 2], "s": [{"x": 1, "y": "a"}, {"x": 2, "y": "b"}]})
+    schema = pl.Schema({"a": pl.Int64, "s": pl.Struct({"x": pl.Int64, "y": pl.String})})
+    out = t.from_arrow_stream(table)
+    assert out.schema == schema
+    assert out.to_dicts() == table.to_pylist()
+
+    out = t.from_arrow_stream(pa.RecordBatchReader.from_batches(table.schema, []))
+    assert out.shape == (0, 2)
+    assert out.schema == schema
+
+
+class BatchesOnly:
+    """Only exposes the record batches, not the arrow C stream."""
+
+    def __init__(self, table):
+        self.schema = table.schema
+        self.to_batches = table.to_batches
+
+
+def test_from_record_batches():
+    # dictionary encoded strings become categoricals
+    table = pa.Table.from_batches([
+        pa.record_batch({"a": [1, 2], "cat": pa.array(["x", "y"]).dictionary_encode()}),
+        pa.record_batch({"a": [3], "cat": pa.array(["x"]).dictionary_encode()}),
+    ])
+    for ob in [table, table.to_reader(), BatchesOnly(table)]:
+        out = t.from_record_batches(ob)
+        assert out.schema == pl.Schema({"a": pl.Int64, "cat": pl.Categorical})
+        assert out.rows() == [(1, "x"), (2, "y"), (3, "x")]
+    out = t.from_record_batches(BatchesOnly(table.schema.empty_table()))
+    assert out.shape == (0, 2)
+    assert out.schema["a"] == pl.Int64
+
+
+def test_arrow_c_stream_export():
+    # frames can be handed to any arrow consumer without copying
+    df = pl.DataFrame({"a": [1, 2], "b": ["x", "y"]})
+    frame = t.to_arrow_frame(pl.concat([df, df], rechunk=False))
+    table = pa.table(frame)
+    assert table.num_rows == 4
+    assert table.to_pydict() == {"a": [1, 2, 1, 2], "b": ["x", "y", "x", "y"]}
+
+    requested = pa.schema([("b", pa.large_string()), ("a", pa.float64())])
+    reader = pa.RecordBatchReader.from_stream(frame, schema=requested)
+    assert reader.schema == requested
+    assert reader.read_all().to_pydict() == {
+        "b": ["x", "y", "x", "y"],
+        "a": [1.0, 2.0, 1.0, 2.0],
+    }
+
+    with pytest.raises(Exception, match="not found"):
+        pa.RecordBatchReader.from_stream(frame, schema=pa.schema([("c", pa.int64())]))
+
+
+def test_select_batches():
+    # frames can be produced batch by batch
+    df = pl.DataFrame({"a": range(10), "b": [str(i) for i in range(10)]})
+    batches = list(t.select_batches(df, 4, ["b"]))
+    assert [batch.height for batch in batches] == [4, 4, 2]
+    assert_frame_equal(pl.concat(batches), df.select("b"))
+    with pytest.raises(pl.exceptions.ColumnNotFoundError):
+        next(t.select_batches(df, 4, ["c"]))
+
+
+def test_sorted_flags():
+    # the sorted flags survive the import of a frame, also through the arrow stream
+    df = pl.DataFrame({
+        "asc": pl.Series([1, 2, 3]).set_sorted(),
+        "desc": pl.Series([3, 2, 1]).set_sorted(descending=True),
+        "not": [2, 1, 3],
+    })
+    expected = ["ascending", "descending", "not"]
+    assert t.column_sorted_flags(df) == expected
+    assert t.frame_roundtrip(df).flags == df.flags
+
+
+def test_to_numpy_structured():
+    df = pl.DataFrame({
+        "i": pl.Series([1, 2], dtype=pl.Int32),
+        "f": [0.5, None],
+        "dt": pl.Series([datetime(2024, 1, 2), datetime(2024, 1, 3)], dtype=pl.Datetime("ms")),
+    })
+    out = t.to_numpy_structured(df)
+    assert isinstance(out, np.ndarray)
+    assert out.dtype == np.dtype([("i", "i4"), ("f", "f8"), ("dt", "datetime64[ms]")])
+    assert out["i"].tolist() == [1, 2]
+    assert out["f"][0] == 0.5 and np.isnan(out["f"][1])
+    assert out["dt"][1] == np.datetime64("2024-01-03", "ms")
+
+
+def test_to_numpy_structured_unsupported():
+    with pytest.raises(TypeError, match="column 's' of dtype str"):
+        t.to_numpy_structured(pl.DataFrame({"s": ["a"]}))
+    with pytest.raises(ValueError, match="column 'i' of dtype i64 contains nulls"):
+        t.to_numpy_structured(pl.DataFrame({"i": [1, None]}))
@@ -0,0 +1,80 @@
+import polars as pl
+import pyarrow as pa
+import pytest
+
+import pyo3_polars_tests as t
+
+
+def test_pushdown_into_scan(tmp_path):
+    path = tmp_path / "data.parquet"
+    pl.DataFrame({"a": [1, 2, 3], "b": [4, 5, 6], "c": [7, 8, 9]}).write_parquet(path)
+    lf = t.lazy_filter_select(pl.scan_parquet(path), pl.col("a") > 1, [pl.col("b")])
+    # the filter and the projection are pushed down into the scan
+    plan = lf.explain()
+    assert "SELECTION" in plan and "FILTER" not in plan, plan
+    assert "PROJECT 2/3 COLUMNS" in plan, plan
+    assert lf.collect().to_dict(as_series=False) == {"b": [5, 6]}
+
+
+def test_anonymous_scan():
+    # a rust data source stays in rust, python queries are pushed into it
+    lf = t.scan_range(10)
+    assert isinstance(lf, pl.LazyFrame)
+    assert lf.collect_schema() == pl.Schema({"i": pl.Int64, "square": pl.Int64})
+    assert lf.filter(pl.col("i") > 6).select("square").collect()["square"].to_list() == [
+        49,
+        64,
+        81,
+    ]
+    assert lf.head(3).collect()["i"].to_list() == [0, 1, 2]
+    assert lf.select(pl.col("square").sum()).collect().item() == 285
+    assert lf.filter(pl.col("i") > 100).collect().shape == (0, 2)
+
+
+def test_inspect_expr():
+    # expressions can be checked against a schema before they are used
+    schema = {"a": pl.Int32, "b": pl.String}
+    expr = (pl.col("a") * 2).alias("x") + pl.col("a").cast(pl.Int64)
+    assert t.inspect_expr(expr, schema) == (["a"], pl.Int64)
+    expr = pl.concat_str([pl.col("b"), pl.col("a")])
+    assert t.inspect_expr(expr, schema) == (["b", "a"], pl.String)
+    with pytest.raises(t.ColumnNotFound):
+        t.inspect_expr(pl.col("missing") + 1, schema)
+
+
+def test_profile():
+    out, timings = t.profile(pl.LazyFrame({"a": [1, 2, 3]}).filter(pl.col("a") > 1))
+    assert out["a"].to_list() == [2, 3]
+    assert timings.columns == ["node", "start", "end"]
+    assert timings.height > 0
+
+
+def test_lazy_from_arrow():
+    table = pa.table({"a": [1, 2], "s": [{"x": 1, "y": "a"}, {"x": 2, "y": "b"}]})
+    lf = t.lazy_from_arrow(table)
+    assert isinstance(lf, pl.LazyFrame)
+    assert lf.collect().to_dicts() == table.to_pylist()
+    df = pl.DataFrame({"a": [1, 2], "b": ["x", "y"]})
+    assert t.lazy_from_arrow(df).collect().equals(df)
+
+
+class FakeLazyFrame:
+    def __init__(self, state):
+        self.state = state
+
+    def __getstate__(self):
+        return self.state
+
+
+def test_plan_of_another_version():
+    # plans of another polars version report the versions, other failures the raw error
+    # a well-formed CBOR map `{"NotAPlan": 1}` that isn't a plan of any polars version
+    with pytest.raises(t.DeserializationError) as e:
+        t.lazy_roundtrip(FakeLazyFrame(b"\xa1\x68NotAPlan\x01"))
+    assert "built against rust polars" in str(e.value)
+    assert f"python polars {pl.__version__}" in str(e.value)
+
+    with pytest.raises(t.DeserializationError) as e:
+        t.lazy_roundtrip(FakeLazyFrame(b"\xa1"))
+    assert "could not deserialize the LazyFrame" in str(e.value)
+    assert "built against" not in str(e.value)
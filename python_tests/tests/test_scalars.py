@@ -0,0 +1,42 @@
+from datetime import date, datetime, timedelta
+from decimal import Decimal
+
+import pytest
+
+import pyo3_polars_tests as t
+
+
+@pytest.mark.parametrize(
+    "value",
+    [
+        None,
+        True,
+        1,
+        2**64 - 1,
+        1.5,
+        "a",
+        b"a",
+        date(2024, 1, 2),
+        datetime(2024, 1, 2, 3, 4),
+        timedelta(days=1),
+        Decimal("1.50"),
+        [1, 2],
+        {"a": 1, "b": "x"},
+    ],
+)
+def test_scalar_roundtrip(value):
+    # scalars are converted like python polars converts them
+    out = t.scalar_roundtrip(value)
+    assert out == value
+    assert type(out) is type(value)
+
+
+def test_unsupported_object():
+    with pytest.raises(TypeError, match="object"):
+        t.scalar_roundtrip(object())
+
+
+@pytest.mark.parametrize("value", [Decimal("NaN"), Decimal("Infinity"), Decimal("1e40")])
+def test_decimal_out_of_range(value):
+    with pytest.raises(ValueError, match="polars decimal"):
+        t.scalar_roundtrip(value)
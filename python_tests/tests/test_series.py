@@ -0,0 +1,153 @@
+import polars as pl
+import pyarrow as pa
+import pytest
+
+import pyo3_polars_tests as t
+
+
+@pytest.mark.parametrize("name", ["", "a", " padded ", "ünïcödé", "with.dot", "0"])
+@pytest.mark.parametrize("values", [[1, 2], [], [None], [["x"]]])
+def test_names_and_dtypes(name, values):
+    s = pl.Series(name, values)
+    out = t.series_roundtrip(s)
+    assert out.name == name
+    assert out.dtype == s.dtype
+
+
+def test_chunks():
+    chunked = pl.concat([pl.Series("chunked", [1]), pl.Series("other", [2])], rechunk=False)
+    assert t.series_roundtrip(chunked).name == "chunked"
+    many_chunks = pl.concat([pl.Series("many", [i]) for i in range(5000)], rechunk=False)
+    assert many_chunks.n_chunks() == 5000
+    out = t.series_roundtrip(many_chunks)
+    assert out.n_chunks() == 1
+    assert out.equals(many_chunks)
+
+
+def test_dtypes():
+    wide = pl.Series("wide", [1, None, 2**100], dtype=pl.Int128)
+    assert t.series_roundtrip(wide).dtype == pl.Int128
+    assert t.series_roundtrip(wide).to_list() == [1, None, 2**100]
+
+    binary = pl.Series("bin", [b"a", None, b"bc"])
+    assert t.to_binary_offset(binary).dtype == pl.Binary
+    assert t.to_binary_offset(binary).to_list() == [b"a", None, b"bc"]
+
+    struct = pl.Series("s", [{"a b": 1, "": "x"}])
+    assert t.series_roundtrip(struct).struct.fields == ["a b", ""]
+
+
+def test_compat_levels():
+    strings = pl.Series("s", ["a", None, "a longer string that isn't inlined in a view"])
+    assert t.series_oldest_compat(strings).equals(strings)
+    # unknown newer levels are clamped
+    for level in [0, 1, 1000]:
+        assert t.series_compat_level(strings, level).equals(strings)
+
+
+def test_to_pyarrow():
+    arr = t.to_pyarrow(pl.Series("a", [1, None, 3]))
+    assert isinstance(arr, pa.Array)
+    assert arr.to_pylist() == [1, None, 3]
+
+    chunked = pl.concat([pl.Series(["a", "b"]), pl.Series(["c"])], rechunk=False)
+    arr = t.to_pyarrow(chunked)
+    assert isinstance(arr, pa.ChunkedArray)
+    assert arr.num_chunks == 2
+    assert arr.type == pa.large_string()
+    assert arr.to_pylist() == ["a", "b", "c"]
+
+
+def test_arrow_c_array_export():
+    # series are read by arrow consumers as a single array
+    s = pl.concat([pl.Series("s", ["x", None]), pl.Series("s", ["y"])], rechunk=False)
+    assert pa.array(t.to_arrow_series(s)).to_pylist() == ["x", None, "y"]
+    arr = pa.array(t.to_arrow_series(s), type=pa.large_string())
+    assert arr.type == pa.large_string()
+    assert arr.to_pylist() == ["x", None, "y"]
+    out = pa.array(t.to_arrow_series(pl.Series([1, 2])), type=pa.float64())
+    assert out.to_pylist() == [1.0, 2.0]
+    assert t.series_roundtrip(t.to_arrow_series(s)).to_list() == ["x", None, "y"]
+
+
+def test_bare_arrow_arrays():
+    # bare arrow arrays are accepted as series, with an empty name
+    out = t.series_roundtrip(pa.array([1, None, 3]))
+    assert out.name == ""
+    assert out.to_list() == [1, None, 3]
+    out = t.series_roundtrip(pa.chunked_array([["a"], ["b", None]]))
+    assert out.dtype == pl.String
+    assert out.to_list() == ["a", "b", None]
+    assert t.series_roundtrip(pa.chunked_array([], type=pa.int8())).dtype == pl.Int8
+
+
+def test_missing_arrow_import(monkeypatch):
+    # a python polars that can't import arrow data from rust raises instead of panicking
+    for name in ("_import_arrow_from_c", "_import_from_c"):
+        if name in pl.Series.__dict__:
+            monkeypatch.delattr(pl.Series, name)
+
+    def no_pyarrow(*args, **kwargs):
+        raise ImportError("pyarrow is not installed")
+
+    monkeypatch.setattr(pl, "from_arrow", no_pyarrow)
+    with pytest.raises(RuntimeError, match="_import_arrow_from_c") as e:
+        t.series_roundtrip(pl.Series([1]))
+    assert isinstance(e.value.__cause__, ImportError)
+
+
+def test_sorted_flag():
+    # the sorted flag survives the conversion in both directions
+    s = pl.Series("a", [1, 2, 3])
+    assert t.sorted_flag(s) == "not"
+    assert t.sorted_flag(s.set_sorted()) == "ascending"
+    assert t.sorted_flag(s.reverse().set_sorted(descending=True)) == "descending"
+    assert t.series_roundtrip(s.set_sorted()).flags["SORTED_ASC"]
+    assert t.series_roundtrip(s.set_sorted(descending=True)).flags["SORTED_DESC"]
+    assert not any(t.series_roundtrip(s).flags.values())
+
+
+def test_nan_to_null():
+    # NaN stays NaN by default, the dedicated extractor turns it into null
+    floats = pl.Series("f", [1.0, float("nan"), None])
+    assert t.series_roundtrip(floats).is_nan().to_list() == [False, True, None]
+    assert t.nan_to_null(floats).to_list() == [1.0, None, None]
+    assert t.nan_to_null(floats.cast(pl.Float32)).to_list() == [1.0, None, None]
+    assert t.nan_to_null(pl.Series([1, None])).to_list() == [1, None]
+
+
+def test_series_list():
+    # any iterable of series can be passed as a list of series
+    a, b = pl.Series("a", [1, 2]), pl.Series("b", ["x", "y"])
+    for columns in [[a, b], (a, b), (s for s in [a, b])]:
+        out = t.reverse_columns(columns)
+        assert isinstance(out, list)
+        assert [s.name for s in out] == ["b", "a"]
+        assert out[0].to_list() == ["x", "y"]
+    with pytest.raises(TypeError, match="item 1"):
+        t.reverse_columns([a, "not a series"])
+
+
+def test_categorical_codes():
+    # the categories of a categorical series are available to build outputs with the same codes
+    s = pl.Series("cat", ["b", "a", "b", "c"], dtype=pl.Categorical)
+    codes = s.to_physical().to_list()
+    out = t.with_codes(s, codes[::-1])
+    assert out.dtype == pl.Categorical
+    assert out.to_list() == ["c", "b", "a", "b"]
+    assert out.to_physical().to_list() == codes[::-1]
+    with pytest.raises(pl.exceptions.OutOfBoundsError):
+        t.with_codes(s, [100])
+
+
+def test_enum():
+    # enum outputs have the same dtype as the python enum, and can be passed in again
+    categories = ["low", "mid", "high"]
+    out = t.to_enum(pl.Series("level", ["mid", None, "low"]), categories)
+    assert out.dtype == pl.Enum(categories)
+    assert out.to_list() == ["mid", None, "low"]
+    assert out.to_physical().to_list() == [1, None, 0]
+    assert t.to_enum(out.cast(pl.String), categories).dtype == out.dtype
+    assert t.series_roundtrip(out).dtype == pl.Enum(categories)
+    with pytest.raises(Exception, match="(?i)enum"):
+        t.to_enum(pl.Series(["extreme"]), categories)